@@ -0,0 +1,9 @@
+#[allow(dead_code, unused_imports)]
+#[path = "../src/bin/03.rs"]
+mod day;
+
+fn main() {
+    let input = &advent_of_code::bench::input(3);
+    advent_of_code::bench::run("part_one", || day::part_one(input));
+    advent_of_code::bench::run("part_two", || day::part_two(input));
+}
@@ -0,0 +1,196 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+//! procedural macros of the template. they are re-exported by `advent_of_code`, use them from
+//! there.
+use proc_macro::{Delimiter, Group, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+/// matches a line against a pattern with typed holes:
+///
+/// ```ignore
+/// let (amount, from, to) = advent_of_code::scan!(line, "move {usize} from {usize} to {usize}")?;
+/// ```
+///
+/// evaluates to `Result<(A, B, ...), ParseError>`, or `Result<A, ParseError>` for a single hole.
+/// text outside the holes must match exactly. a hole takes everything up to the first occurrence
+/// of the text after it, or the rest of the line, and parses it with `FromStr`. two holes need
+/// text between them. `{{` and `}}` match literal braces.
+#[proc_macro]
+pub fn scan(input: TokenStream) -> TokenStream {
+    match expand(input) {
+        Ok(output) => output,
+        Err((span, message)) => compile_error(span, &message),
+    }
+}
+
+enum Piece {
+    Text(String),
+    Hole(String),
+}
+
+type Error = (Span, String);
+
+fn expand(input: TokenStream) -> Result<TokenStream, Error> {
+    let mut tokens = input.into_iter().collect::<Vec<_>>();
+    // an optional trailing comma
+    if matches!(tokens.last(), Some(TokenTree::Punct(p)) if p.as_char() == ',') {
+        tokens.pop();
+    }
+    let usage = "expected `scan!(line, \"pattern\")`".to_string();
+    let pattern = match tokens.pop() {
+        Some(TokenTree::Literal(literal)) => literal,
+        Some(other) => return Err((other.span(), usage)),
+        None => return Err((Span::call_site(), usage)),
+    };
+    match tokens.pop() {
+        Some(TokenTree::Punct(p)) if p.as_char() == ',' && !tokens.is_empty() => {}
+        _ => return Err((pattern.span(), usage)),
+    }
+    let text = string_value(&pattern).ok_or_else(|| {
+        (
+            pattern.span(),
+            "the pattern must be a string literal".to_string(),
+        )
+    })?;
+    let pieces = pieces(&text).map_err(|message| (pattern.span(), message))?;
+
+    let mut body = String::new();
+    let mut holes = Vec::new();
+    for (i, piece) in pieces.iter().enumerate() {
+        match piece {
+            Piece::Text(text) => {
+                body += &format!(
+                    "::advent_of_code::helpers::combinator::literal({:?})(cursor)?;",
+                    text
+                )
+            }
+            Piece::Hole(ty) => {
+                let terminator = match pieces.get(i + 1) {
+                    Some(Piece::Text(text)) => text.as_str(),
+                    _ => "",
+                };
+                body += &format!(
+                    "let hole_{} = ::advent_of_code::helpers::scan::hole::<{}>({:?})(cursor)?;",
+                    holes.len(),
+                    ty,
+                    terminator
+                );
+                holes.push(format!("hole_{}", holes.len()));
+            }
+        }
+    }
+    let result = match &holes[..] {
+        [one] => one.clone(),
+        _ => format!(
+            "({})",
+            holes.iter().map(|h| format!("{},", h)).collect::<String>()
+        ),
+    };
+    let parser = format!(
+        "|cursor: &mut ::advent_of_code::helpers::combinator::Cursor<'_>| \
+         -> ::std::result::Result<_, ::advent_of_code::helpers::combinator::Failure> {{ \
+         {} ::std::result::Result::Ok({}) }}",
+        body, result
+    );
+    let parser = parser
+        .parse::<TokenStream>()
+        .map_err(|e| (pattern.span(), format!("invalid hole type: {}", e)))?;
+
+    let mut arguments = parser;
+    arguments.extend([TokenTree::Punct(Punct::new(',', Spacing::Alone))]);
+    arguments.extend(tokens);
+    let mut output = "::advent_of_code::helpers::combinator::parse"
+        .parse::<TokenStream>()
+        .unwrap();
+    output.extend([TokenTree::Group(Group::new(
+        Delimiter::Parenthesis,
+        arguments,
+    ))]);
+    Ok(output)
+}
+
+/// splits a pattern into literal text and holes.
+fn pieces(pattern: &str) -> Result<Vec<Piece>, String> {
+    let mut pieces = Vec::new();
+    let mut text = String::new();
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                text.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                text.push('}');
+            }
+            '{' => {
+                let ty = chars.by_ref().take_while(|&c| c != '}').collect::<String>();
+                let ty = ty.trim();
+                if ty.is_empty() {
+                    return Err("holes need a type, e.g. `{usize}`".to_string());
+                }
+                if !text.is_empty() {
+                    pieces.push(Piece::Text(std::mem::take(&mut text)));
+                } else if matches!(pieces.last(), Some(Piece::Hole(_))) {
+                    return Err(format!(
+                        "the hole `{{{}}}` needs text between it and the hole before it",
+                        ty
+                    ));
+                }
+                pieces.push(Piece::Hole(ty.to_string()));
+            }
+            '}' => return Err("unmatched `}`, use `}}` for a literal brace".to_string()),
+            c => text.push(c),
+        }
+    }
+    if !text.is_empty() {
+        pieces.push(Piece::Text(text));
+    }
+    Ok(pieces)
+}
+
+/// the value of a string literal, plain or raw.
+fn string_value(literal: &Literal) -> Option<String> {
+    let source = literal.to_string();
+    if let Some(raw) = source.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        let quoted = &raw[hashes..raw.len() - hashes];
+        return Some(quoted.strip_prefix('"')?.strip_suffix('"')?.to_string());
+    }
+    let quoted = source.strip_prefix('"')?.strip_suffix('"')?;
+    let mut value = String::new();
+    let mut chars = quoted.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        match chars.next()? {
+            'n' => value.push('\n'),
+            't' => value.push('\t'),
+            'r' => value.push('\r'),
+            '0' => value.push('\0'),
+            c @ ('\\' | '"' | '\'') => value.push(c),
+            // a line continuation skips the line break and the indentation after it
+            '\n' => {
+                let rest = chars.as_str().trim_start();
+                chars = rest.chars();
+            }
+            _ => return None,
+        }
+    }
+    Some(value)
+}
+
+fn compile_error(span: Span, message: &str) -> TokenStream {
+    let mut literal = Literal::string(message);
+    literal.set_span(span);
+    let mut output = "::core::compile_error!".parse::<TokenStream>().unwrap();
+    output.extend([TokenTree::Group(Group::new(
+        Delimiter::Parenthesis,
+        TokenStream::from(TokenTree::Literal(literal)),
+    ))]);
+    output
+}
@@ -0,0 +1,76 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::fmt::Display;
+
+use crate::{config, crypt};
+
+/// known answers for a day, stored in `src/answers/NN.txt` as `1: <answer>` and `2: <answer>`
+/// lines. used to flag regressions when a solution is changed.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct Answers {
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+impl Answers {
+    pub fn parse(text: &str) -> Self {
+        let mut answers = Answers::default();
+        for line in text.lines() {
+            match line.split_once(':') {
+                Some(("1", answer)) => answers.part_one = Some(answer.trim().to_string()),
+                Some(("2", answer)) => answers.part_two = Some(answer.trim().to_string()),
+                _ => {}
+            }
+        }
+        answers
+    }
+
+    /// loads the known answers for `day`, decrypting them if necessary.
+    /// a missing file means no answers are known yet.
+    pub fn load(day: u8) -> Self {
+        let config = config::get();
+        crypt::read(&config.paths.answer(day), &config.encryption)
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+            .map(|text| Answers::parse(&text))
+            .unwrap_or_default()
+    }
+
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_one.as_deref(),
+            2 => self.part_two.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(answer) = &self.part_one {
+            writeln!(f, "1: {}", answer)?;
+        }
+        if let Some(answer) = &self.part_two {
+            writeln!(f, "2: {}", answer)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let answers = Answers::parse("1: 24000\n2:  45000 \n");
+        assert_eq!(answers.get(1), Some("24000"));
+        assert_eq!(answers.get(2), Some("45000"));
+        assert_eq!(Answers::parse(&answers.to_string()), answers);
+
+        assert_eq!(Answers::parse("1: CMZ\n").get(2), None);
+        assert_eq!(Answers::parse(""), Answers::default());
+    }
+}
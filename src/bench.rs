@@ -0,0 +1,134 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::{
+    hint::black_box,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use crate::{config, crypt, Normalize};
+
+/// how long each part is measured for.
+const MEASURE_FOR: Duration = Duration::from_secs(2);
+const MIN_RUNS: u32 = 10;
+
+/// the input a benchmark runs against: the real input, or the example while the input has not
+/// been downloaded yet. panics if neither exists.
+pub fn input(day: u8) -> String {
+    input_with(day, Normalize::default())
+}
+
+/// like `input`, cleaned up the way the day reads its input with `read_file_with`.
+pub fn input_with(day: u8, normalize: Normalize) -> String {
+    let config = config::get();
+    let text = |path: &Path| {
+        crypt::read(path, &config.encryption)
+            .ok()
+            .filter(|bytes| !bytes.is_empty())
+            .map(|bytes| normalize.apply(&String::from_utf8_lossy(&bytes)))
+    };
+
+    text(&config.paths.input(day)).unwrap_or_else(|| {
+        println!(
+            "Input for day {} is missing, benchmarking against the example.",
+            day
+        );
+        text(&config.paths.example(day)).unwrap_or_else(|| {
+            panic!(
+                "neither the input nor the example of day {} exists, nothing to benchmark",
+                day
+            )
+        })
+    })
+}
+
+/// timings of one benchmarked function.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Measurement {
+    pub runs: u32,
+    pub mean: Duration,
+    pub min: Duration,
+    pub max: Duration,
+}
+
+/// running totals of the samples of a measurement, so that long measurements of fast functions
+/// do not keep millions of samples around.
+#[derive(Default)]
+struct Samples {
+    runs: u32,
+    total: Duration,
+    min: Option<Duration>,
+    max: Duration,
+}
+
+impl Samples {
+    fn push(&mut self, sample: Duration) {
+        self.runs += 1;
+        self.total += sample;
+        self.min = Some(self.min.map_or(sample, |min| min.min(sample)));
+        self.max = self.max.max(sample);
+    }
+
+    fn measurement(&self) -> Measurement {
+        Measurement {
+            runs: self.runs,
+            mean: self.total / self.runs.max(1),
+            min: self.min.unwrap_or_default(),
+            max: self.max,
+        }
+    }
+}
+
+/// calls `func` repeatedly for about two seconds, at least ten times.
+pub fn measure<T>(func: impl FnMut() -> T) -> Measurement {
+    measure_for(func, MEASURE_FOR)
+}
+
+/// calls `func` repeatedly for about `duration`, at least ten times.
+pub fn measure_for<T>(mut func: impl FnMut() -> T, duration: Duration) -> Measurement {
+    // warm up caches and lazily initialised state.
+    black_box(func());
+
+    let started = Instant::now();
+    let mut samples = Samples::default();
+    while samples.runs < MIN_RUNS || started.elapsed() < duration {
+        let timer = Instant::now();
+        black_box(func());
+        samples.push(timer.elapsed());
+    }
+    samples.measurement()
+}
+
+/// measures `func` and prints its timings under `name`.
+pub fn run<T>(name: &str, func: impl FnMut() -> T) {
+    let m = measure(func);
+    println!(
+        "{}: mean {:.2?}, min {:.2?}, max {:.2?} ({} runs)",
+        name, m.mean, m.min, m.max, m.runs
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summarise_samples() {
+        let mut samples = Samples::default();
+        for sample in [3, 1, 2].map(Duration::from_millis) {
+            samples.push(sample);
+        }
+        assert_eq!(Samples::default().measurement().runs, 0);
+        assert_eq!(
+            samples.measurement(),
+            Measurement {
+                runs: 3,
+                mean: Duration::from_millis(2),
+                min: Duration::from_millis(1),
+                max: Duration::from_millis(3),
+            }
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use advent_of_code::helpers::select::top_k;
+
 #[derive(Ord, PartialOrd, Eq, PartialEq, Copy, Clone)]
 struct Elf(u32);
 
@@ -14,13 +16,11 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let mut elves = get_elves(input);
-    elves.sort_unstable();
+    let elves = top_k(get_elves(input), 3);
     if elves.len() < 3 {
         return None;
     }
-    elves.reverse();
-    Some(elves.iter().take(3).map(|x| x.0).sum())
+    Some(elves.iter().map(|x| x.0).sum())
 }
 
 fn main() {
@@ -1,7 +1,9 @@
+use std::fmt::{Display, Formatter};
 use std::str::FromStr;
-use crate::Condition::{Draw, Lose, Win};
-use crate::RoundParseError::{FormatError, Shape1Error, Shape2Error};
-use crate::Shape::{Paper, Rock, Scissors};
+use advent_of_code::helpers::parse::{parse_lines, ParseError};
+use self::Condition::{Draw, Lose, Win};
+use self::RoundParseError::{FormatError, Shape1Error, Shape2Error};
+use self::Shape::{Paper, Rock, Scissors};
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 enum Shape {
@@ -38,15 +40,31 @@ enum RoundParseError {
     Shape2Error,
 }
 
+impl Display for RoundParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FormatError => write!(f, "invalid format, expected two letters separated by a space"),
+            Shape1Error => write!(f, "bad character in shape 1"),
+            Shape2Error => write!(f, "bad character in shape 2"),
+        }
+    }
+}
+
+/// checks the `A X` format, returning both characters.
+fn split_round(s: &str) -> Result<(char, char), ParseError> {
+    match s.chars().collect::<Vec<_>>()[..] {
+        [c1, ' ', c2] => Ok((c1, c2)),
+        _ => Err(ParseError::whole(s, FormatError)),
+    }
+}
+
 impl FromStr for Round {
-    type Err = RoundParseError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.len() != 3 || s.chars().nth(1).unwrap() != ' ' {
-            return Err(FormatError);
-        }
-        Ok(Round(char_1_to_shape(s.chars().nth(0).unwrap()).ok_or(Shape1Error)?,
-                 char_2_to_shape(s.chars().nth(2).unwrap()).ok_or(Shape2Error)?))
+        let (c1, c2) = split_round(s)?;
+        Ok(Round(char_1_to_shape(c1).ok_or_else(|| ParseError::new(s, 0, Shape1Error))?,
+                 char_2_to_shape(c2).ok_or_else(|| ParseError::new(s, 2, Shape2Error))?))
     }
 }
 
@@ -61,18 +79,16 @@ enum Condition {
 struct Round2(Shape, Condition);
 
 impl FromStr for Round2 {
-    type Err = RoundParseError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.len() != 3 || s.chars().nth(1).unwrap() != ' ' {
-            return Err(FormatError);
-        }
-        Ok(Round2(char_1_to_shape(s.chars().nth(0).unwrap()).ok_or(Shape1Error)?,
-                  match s.chars().nth(2).unwrap() {
+        let (c1, c2) = split_round(s)?;
+        Ok(Round2(char_1_to_shape(c1).ok_or_else(|| ParseError::new(s, 0, Shape1Error))?,
+                  match c2 {
                       'X' => Lose,
                       'Y' => Draw,
                       'Z' => Win,
-                      _ => return Err(Shape2Error)
+                      _ => return Err(ParseError::new(s, 2, Shape2Error))
                   },
         ))
     }
@@ -104,20 +120,8 @@ fn calc_points(round: &Round) -> u32 {
     win_points + choice_points
 }
 
-fn parse_rounds<T: FromStr<Err = RoundParseError>>(input: &str) -> Option<Vec<T>> {
-    input.lines().enumerate().map(
-        |(line_num, line)| {
-            line.parse::<T>().map_err(|err| {
-                println!("Error or line {line_num}: {}", match err {
-                    FormatError => "invalid format".to_string(),
-                    _ => "bad character in shape ".to_string() + match err {
-                        Shape1Error => "1",
-                        Shape2Error => "2",
-                        _ => "",
-                    }
-                });
-            }).ok()
-        }).collect::<Option<Vec<_>>>()
+fn parse_rounds<T: FromStr<Err = ParseError>>(input: &str) -> Option<Vec<T>> {
+    parse_lines(input).map_err(|errors| eprintln!("{errors}")).ok()
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
@@ -138,7 +142,7 @@ fn main() {
 
 #[cfg(test)]
 mod tests {
-    use crate::Shape::{Paper, Rock, Scissors};
+    use super::Shape::{Paper, Rock, Scissors};
     use super::*;
 
     #[test]
@@ -153,6 +157,14 @@ mod tests {
         assert_eq!(part_two(&input), Some(12));
     }
 
+    #[test]
+    fn parse_errors() {
+        let errors = parse_lines::<Round>("A Y\nB Q\nC\n").unwrap_err().0;
+        assert_eq!((errors[0].line, errors[0].column), (2, 3));
+        assert_eq!(errors[0].cause, "bad character in shape 2");
+        assert_eq!((errors[1].line, errors[1].column), (3, 1));
+    }
+
     #[test]
     fn test_points() {
         assert_eq!(calc_points(&Round(Rock, Rock)), 1 + 3);
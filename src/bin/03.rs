@@ -1,6 +1,8 @@
 use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
 use std::str::FromStr;
-use crate::BackpackParseError::{BadCharacter, WrongSizes};
+use advent_of_code::helpers::parse::{parse_lines, ParseError};
+use self::BackpackParseError::{BadCharacter, WrongSizes};
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
 struct Item(char);
@@ -32,6 +34,15 @@ enum BackpackParseError {
     BadCharacter,
 }
 
+impl Display for BackpackParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WrongSizes => write!(f, "compartments must have the same size"),
+            BadCharacter => write!(f, "items must be letters"),
+        }
+    }
+}
+
 impl Backpack {
     fn new(compartment1: &[Item], compartment2: &[Item]) -> Result<Self, BackpackParseError> {
         if compartment1.len() != compartment2.len() {
@@ -51,21 +62,26 @@ impl Backpack {
 }
 
 impl FromStr for Backpack {
-    type Err = BackpackParseError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let items = s.chars().map(Item::new).collect::<Option<Vec<_>>>().ok_or(BadCharacter)?;
+        let items = s.char_indices().map(|(offset, c)| {
+            Item::new(c).ok_or_else(|| ParseError::new(s, offset, BadCharacter))
+        }).collect::<Result<Vec<_>, _>>()?;
         if items.len() % 2 != 0 {
-            return Err(WrongSizes);
+            return Err(ParseError::whole(s, WrongSizes));
         }
         let (c1, c2) = items.split_at(items.len() / 2);
-        Backpack::new(c1, c2)
+        Backpack::new(c1, c2).map_err(|err| ParseError::whole(s, err))
     }
 }
 
+fn parse_backpacks(input: &str) -> Option<Vec<Backpack>> {
+    parse_lines(input).map_err(|errors| eprintln!("{errors}")).ok()
+}
+
 pub fn part_one(input: &str) -> Option<u32> {
-    let lines = input.lines();
-    let backpacks = lines.map(|line| line.parse::<Backpack>().ok()).collect::<Option<Vec<_>>>()?;
+    let backpacks = parse_backpacks(input)?;
     Some(backpacks
         .iter()
         .map(|backpack| backpack.score().map(|x| x as u32))
@@ -75,8 +91,7 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let backpacks = input.lines()
-        .map(|line| line.parse::<Backpack>().ok()).collect::<Option<Vec<_>>>()?;
+    let backpacks = parse_backpacks(input)?;
 
     backpacks.chunks(3).map(|chunk| {
         let mut opt: Vec<HashSet<Item>> = chunk.iter()
@@ -120,19 +135,19 @@ mod tests {
     fn backpack_parse() {
         assert_eq!("qheavsrt".parse(), Ok(Backpack(HashSet::from([Item('q'), Item('h'), Item('e'), Item('a')]),
                                                    HashSet::from([Item('v'), Item('s'), Item('r'), Item('t')]))));
-        assert_eq!("qhea-srt".parse::<Backpack>(), Err(BadCharacter));
-        assert_eq!("qheavsr".parse::<Backpack>(), Err(WrongSizes));
+        assert_eq!("qhea-srt".parse::<Backpack>(), Err(ParseError::new("qhea-srt", 4, BadCharacter)));
+        assert_eq!("qheavsr".parse::<Backpack>(), Err(ParseError::whole("qheavsr", WrongSizes)));
     }
 
     #[test]
-    fn backpack_shared() -> Result<(), BackpackParseError> {
+    fn backpack_shared() -> Result<(), ParseError> {
         assert_eq!("abcddefg".parse::<Backpack>()?.shared_item(), Some(Item('d')));
         assert_eq!("abcdefgh".parse::<Backpack>()?.shared_item(), None);
         Ok(())
     }
 
     #[test]
-    fn backpack_score() -> Result<(), BackpackParseError> {
+    fn backpack_score() -> Result<(), ParseError> {
         assert_eq!("abcddefg".parse::<Backpack>()?.score(), Some(4));
         assert_eq!("abczzefg".parse::<Backpack>()?.score(), Some(26));
         assert_eq!("abcdefgh".parse::<Backpack>()?.score(), None);
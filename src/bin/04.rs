@@ -1,51 +1,13 @@
-use std::num::ParseIntError;
 use std::str::FromStr;
-use crate::RangePairParseError::BadRange;
-use crate::RangeParseError::{BadInt, BadFormat};
+use advent_of_code::helpers::interval::Interval;
+use advent_of_code::helpers::parse::{parse_lines, ParseError};
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
-struct Range(u8, u8);
-
-impl Range {
-    fn contains(&self, other: &Range) -> bool {
-        self.0 <= other.0 && self.1 >= other.1
-    }
-
-    fn overlaps(&self, other: &Range) -> bool {
-        self.0 <= other.1 && self.1 >= other.0
-    }
-}
-
-#[derive(Clone, Eq, PartialEq, Debug)]
-enum RangeParseError {
-    BadFormat,
-    BadInt(ParseIntError),
-}
-
-impl From<ParseIntError> for RangeParseError {
-    fn from(value: ParseIntError) -> Self {
-        BadInt(value)
-    }
-}
-
-impl FromStr for Range {
-    type Err = RangeParseError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (n1, n2) = s.split_once("-").ok_or(BadFormat)?;
-        Ok(Range(
-            n1.parse()?,
-            n2.parse()?,
-        ))
-    }
-}
-
-#[derive(Copy, Clone, Eq, PartialEq, Debug)]
-struct RangePair(Range, Range);
+struct RangePair(Interval<u32>, Interval<u32>);
 
 impl RangePair {
     fn contains(&self) -> bool {
-        self.0.contains(&self.1) || self.1.contains(&self.0)
+        self.0.covers(&self.1) || self.1.covers(&self.0)
     }
 
     fn overlaps(&self) -> bool {
@@ -53,36 +15,17 @@ impl RangePair {
     }
 }
 
-#[derive(Clone, Eq, PartialEq, Debug)]
-enum RangePairParseError {
-    BadFormat,
-    BadRange(RangeParseError),
-}
-
-impl From<RangeParseError> for RangePairParseError {
-    fn from(value: RangeParseError) -> Self {
-        BadRange(value)
-    }
-}
-
 impl FromStr for RangePair {
-    type Err = RangePairParseError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (r1, r2) = s.split_once(",").ok_or(RangePairParseError::BadFormat)?;
-        Ok(RangePair(
-            r1.parse()?,
-            r2.parse()?,
-        ))
+        let (a, b, c, d) = advent_of_code::scan!(s, "{u32}-{u32},{u32}-{u32}")?;
+        Ok(RangePair(Interval::inclusive(a, b), Interval::inclusive(c, d)))
     }
 }
 
 fn parse_pairs(input: &str) -> Option<Vec<RangePair>> {
-    input.lines().enumerate().map(|(line_num, line)| {
-        line.parse::<RangePair>().map_err(|err| {
-            println!("Error on line {}: {err:?}", line_num + 1)
-        }).ok()
-    }).collect::<Option<Vec<_>>>()
+    parse_lines(input).map_err(|errors| eprintln!("{errors}")).ok()
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
@@ -119,22 +62,25 @@ mod tests {
 
     #[test]
     fn parse_range() {
-        assert_eq!("11-22,33-44".parse(), Ok(RangePair(Range(11, 22), Range(33, 44))));
-        assert_eq!("a1-22,33-44".parse::<RangePair>(), Err(BadRange(BadInt("a1".parse::<u8>().unwrap_err()))));
-        assert_eq!("11-22:33-44".parse::<RangePair>(), Err(RangePairParseError::BadFormat));
+        assert_eq!("11-22,33-44".parse(), Ok(RangePair(Interval::inclusive(11, 22), Interval::inclusive(33, 44))));
+        let err = "11-22,3a-44".parse::<RangePair>().unwrap_err();
+        assert_eq!((err.column, err.len), (7, 2));
+        assert_eq!(err.cause, "invalid digit found in string");
+        let err = "11-22:33-44".parse::<RangePair>().unwrap_err();
+        assert_eq!((err.column, err.cause.as_str()), (6, "expected \",\""));
     }
 
     #[test]
     fn contains() {
-        assert!(RangePair(Range(11, 44), Range(22, 33)).contains());
-        assert!(!RangePair(Range(11, 33), Range(22, 44)).contains());
-        assert!(!RangePair(Range(11, 22), Range(33, 44)).contains());
+        assert!(RangePair(Interval::inclusive(11, 44), Interval::inclusive(22, 33)).contains());
+        assert!(!RangePair(Interval::inclusive(11, 33), Interval::inclusive(22, 44)).contains());
+        assert!(!RangePair(Interval::inclusive(11, 22), Interval::inclusive(33, 44)).contains());
     }
 
     #[test]
     fn overlaps() {
-        assert!(RangePair(Range(11, 44), Range(22, 33)).overlaps());
-        assert!(RangePair(Range(11, 33), Range(22, 44)).overlaps());
-        assert!(!RangePair(Range(11, 22), Range(33, 44)).overlaps());
+        assert!(RangePair(Interval::inclusive(11, 44), Interval::inclusive(22, 33)).overlaps());
+        assert!(RangePair(Interval::inclusive(11, 33), Interval::inclusive(22, 44)).overlaps());
+        assert!(!RangePair(Interval::inclusive(11, 22), Interval::inclusive(33, 44)).overlaps());
     }
 }
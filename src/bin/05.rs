@@ -1,10 +1,10 @@
 use std::fmt::{Display, Formatter};
 use std::num::ParseIntError;
 use std::str::FromStr;
-use crate::CommandApplyError::{BadAmount, BadFromIndex, BadToIndex};
-use crate::CommandParseError::{BadInt, BadString};
-use crate::CrateCollectionParseError::{BadCrate, BadFormat, BadNumberParse, BadNumberSequence, BadStacking};
-use crate::CrateParseError::{BadCharacter, BadLength};
+use advent_of_code::helpers::parse::{parse_lines, ParseError};
+use self::CommandApplyError::{BadAmount, BadFromIndex, BadToIndex};
+use self::CrateCollectionParseError::{BadCrate, BadFormat, BadNumberParse, BadNumberSequence, BadStacking};
+use self::CrateParseError::{BadCharacter, BadLength};
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 struct Crate(char);
@@ -138,31 +138,11 @@ impl FromStr for CrateCollection {
     }
 }
 
-#[derive(Clone, Eq, PartialEq, Debug)]
-enum CommandParseError {
-    BadString,
-    BadInt(ParseIntError),
-}
-
 impl FromStr for Command {
-    type Err = CommandParseError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let words = s.split_whitespace().collect::<Vec<_>>();
-        if words.len() != 6 {
-            return Err(BadString);
-        }
-        words.iter().enumerate().map(|(num, str)| {
-            match num {
-                0 => *str == "move",
-                2 => *str == "from",
-                4 => *str == "to",
-                _ => true,
-            }
-        }).all(|b| b).then(|| ()).ok_or(BadString)?;
-        let amount = words.get(1).unwrap().parse().map_err(|err| BadInt(err))?;
-        let from = words.get(3).unwrap().parse().map_err(|err| BadInt(err))?;
-        let to = words.get(5).unwrap().parse().map_err(|err| BadInt(err))?;
+        let (amount, from, to) = advent_of_code::scan!(s, "move {usize} from {usize} to {usize}")?;
         Ok(Self { amount, from, to })
     }
 }
@@ -194,8 +174,15 @@ impl Display for Command {
 
 fn parse_input(input: &str) -> Option<(CrateCollection, Vec<Command>)> {
     let (crate_str, command_str) = input.split_once("\n\n")?;
-    let crates = crate_str.parse::<CrateCollection>().ok()?;
-    Some((crates, command_str.lines().map(|line| line.parse().ok()).collect::<Option<Vec<_>>>()?))
+    let crates = crate_str.parse::<CrateCollection>()
+        .map_err(|err| eprintln!("Could not parse the crates: {err:?}")).ok()?;
+    // the commands start after the crates and the blank line
+    let commands = parse_lines(command_str).map_err(|mut errors| {
+        let first_line = crate_str.lines().count() + 1;
+        errors.0 = errors.0.into_iter().map(|err| err.shift_lines(first_line)).collect();
+        eprintln!("{errors}")
+    }).ok()?;
+    Some((crates, commands))
 }
 
 pub fn part_one(input: &str) -> Option<String> {
@@ -250,6 +237,15 @@ mod tests {
         assert_eq!(part_two(&input), Some("MCD".to_string()));
     }
 
+    #[test]
+    fn parse_command() {
+        assert_eq!("move 3 from 1 to 2".parse(), Ok(Command { amount: 3, from: 1, to: 2 }));
+        let err = "move 3 form 1 to 2".parse::<Command>().unwrap_err();
+        assert_eq!((err.column, err.cause.as_str()), (7, "expected \" from \""));
+        let err = "move 3 from x to 2".parse::<Command>().unwrap_err();
+        assert_eq!((err.column, err.cause.as_str()), (13, "invalid digit found in string"));
+    }
+
     #[test]
     fn parse_crates() {
         assert_eq!(
@@ -1,4 +1,5 @@
 use std::collections::BTreeSet;
+use advent_of_code::Normalize;
 
 fn is_marker(chars: &[char]) -> bool {
     let mut set = BTreeSet::new();
@@ -16,18 +17,57 @@ fn find_window(input: &str, size: usize) -> Option<u32> {
         .map(|result| (result + size) as u32)
 }
 
+/// a window is a marker if its letters set as many distinct bits as it is long. only works for
+/// lowercase letters, other input takes the generic path.
+fn find_window_bitmask(input: &str, size: usize) -> Option<u32> {
+    if !input.bytes().all(|byte| byte.is_ascii_lowercase()) {
+        return find_window(input, size);
+    }
+    input.as_bytes().windows(size)
+        .position(|window| {
+            let bits = window.iter().fold(0u32, |bits, byte| bits | 1 << (byte - b'a'));
+            bits.count_ones() as usize == size
+        })
+        .map(|result| (result + size) as u32)
+}
+
 pub fn part_one(input: &str) -> Option<u32> {
     find_window(input, 4)
 }
 
+pub fn part_one_bitmask(input: &str) -> Option<u32> {
+    find_window_bitmask(input, 4)
+}
+
 pub fn part_two(input: &str) -> Option<u32> {
     find_window(input, 14)
 }
 
+pub fn part_two_bitmask(input: &str) -> Option<u32> {
+    find_window_bitmask(input, 14)
+}
+
+/// a datastream over a small alphabet, so that markers are found in some inputs but not all.
+fn generate_input(rng: &mut advent_of_code::fuzz::Rng) -> String {
+    let letters = rng.range(1..=20) as u8;
+    (0..rng.usize(0..=60)).map(|_| char::from(b'a' + rng.range(0..=u64::from(letters - 1)) as u8)).collect()
+}
+
+/// the datastream is a single line, without the line break the file ends with. the bench reads
+/// its input the same way.
+pub fn normalize() -> Normalize {
+    Normalize::default().trim_trailing_newlines()
+}
+
+fn read_datastream(folder: &str) -> String {
+    advent_of_code::read_file_with(folder, 6, normalize())
+}
+
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 6);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::fuzz_diff!(generate_input, 1 => [part_one, part_one_bitmask], 2 => [part_two, part_two_bitmask]);
+    let input = &read_datastream("inputs");
+    advent_of_code::solve!(1, input, [part_one, part_one_bitmask]);
+    advent_of_code::solve!(2, input, [part_two, part_two_bitmask]);
 }
 
 #[cfg(test)]
@@ -36,7 +76,7 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 6);
+        let input = read_datastream("examples");
         assert_eq!(part_one(&input), Some(7));
 
         assert_eq!(part_one("bvwbjplbgvbhsrlpgdmjqwftvncz"), Some(5));
@@ -47,7 +87,7 @@ mod tests {
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 6);
+        let input = read_datastream("examples");
         assert_eq!(part_two(&input), Some(19));
 
         assert_eq!(part_two("bvwbjplbgvbhsrlpgdmjqwftvncz"), Some(23));
@@ -55,4 +95,16 @@ mod tests {
         assert_eq!(part_two("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), Some(29));
         assert_eq!(part_two("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), Some(26));
     }
+
+    #[test]
+    fn test_implementations_agree() {
+        let input = read_datastream("examples");
+        for input in [input.as_str(), "bvwbjplbgvbhsrlpgdmjqwftvncz", "nppdvjthqldpwncqszvftbrmjlhg", "aaaa"] {
+            assert_eq!(part_one_bitmask(input), part_one(input));
+            assert_eq!(part_two_bitmask(input), part_two(input));
+        }
+        for input in ["ab!cdef", "abcD", "aé€b"] {
+            assert_eq!(part_one_bitmask(input), part_one(input));
+        }
+    }
 }
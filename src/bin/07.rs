@@ -1,12 +1,10 @@
 use std::fmt::{Display, Formatter};
-use std::num::ParseIntError;
 use std::str::FromStr;
 use derive_more::From;
-use crate::ChangeDirPath::{Dir, Up};
-use crate::Command::{ChangeDir, List};
-use crate::ParseCommandError::{BadCommand, BadLs};
-use crate::ParseFileError::{BadFormat};
-use crate::ParseNameError::Empty;
+use advent_of_code::helpers::combinator::{alt, integer, literal, parse, seq, whitespace, word, Parser};
+use advent_of_code::helpers::parse::{parse_lines, ParseError};
+use self::ChangeDirPath::{Dir, Up};
+use self::Command::{ChangeDir, List};
 
 #[derive(Clone, Eq, PartialEq, Debug, Default)]
 struct Name(String);
@@ -36,110 +34,32 @@ enum Command {
     FsNode(FsNode),
 }
 
-#[derive(Clone, Eq, PartialEq, Debug)]
-enum ParseNameError {
-    Empty,
-}
-
-impl FromStr for Name {
-    type Err = ParseNameError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.is_empty() {
-            Err(Empty)
-        } else {
-            Ok(Self(s.to_string()))
-        }
-    }
-}
-
-impl FromStr for ChangeDirPath {
-    type Err = ParseNameError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            ".." => Ok(Up),
-            _ => Ok(Dir(s.parse()?))
-        }
-    }
+fn name() -> impl Parser<Name> {
+    word().map(Name)
 }
 
-#[derive(Clone, Eq, PartialEq, Debug, From)]
-enum ParseFileError {
-    BadInt(ParseIntError),
-    BadName(ParseNameError),
-    BadFormat,
+fn fs_node() -> impl Parser<FsNode> {
+    alt((
+        literal("dir ").then(name()).map(|name| FsNode::Dir(Directory::new(name))),
+        seq((integer(), whitespace(), name())).map(|(size, _, name)| FsNode::File(File(size, name))),
+    ))
 }
 
-impl FromStr for File {
-    type Err = ParseFileError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (int_str, name) = s.split_once(|c: char| c.is_whitespace()).ok_or(BadFormat)?;
-        let size = int_str.parse()?;
-        Ok(Self(size, name.parse()?))
-    }
-}
-
-#[derive(Clone, Eq, PartialEq, Debug, From)]
-enum ParseDirectoryError {
-    BadName(ParseNameError),
-    BadFormat,
-}
-
-impl FromStr for Directory {
-    type Err = ParseDirectoryError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Directory::new(s.strip_prefix("dir")
-            .and_then(|s| s.strip_prefix(|c: char| c.is_whitespace()))
-            .ok_or(ParseDirectoryError::BadFormat)?.parse()?))
-    }
-}
-
-#[derive(Clone, Eq, PartialEq, Debug, From)]
-enum ParseFsNodeError {
-    BadFile(ParseFileError),
-    BadDir(ParseDirectoryError),
-}
-
-impl FromStr for FsNode {
-    type Err = ParseFsNodeError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.strip_prefix("dir") {
-            Some(_) => Ok(Self::Dir(s.parse()?)),
-            None => Ok(Self::File(s.parse()?)),
-        }
-    }
-}
-
-#[derive(Clone, Eq, PartialEq, Debug, From)]
-enum ParseCommandError {
-    BadCommand,
-    BadCd(ParseNameError),
-    BadLs,
-    BadFsNode(ParseFsNodeError),
+fn command() -> impl Parser<Command> {
+    alt((
+        literal("$ ").then(alt((
+            literal("cd ").then(name()).map(|name| ChangeDir(if name.0 == ".." { Up } else { Dir(name) })),
+            literal("ls").map(|_| List),
+        ))),
+        fs_node().map(Command::FsNode),
+    ))
 }
 
 impl FromStr for Command {
-    type Err = ParseCommandError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut split = s.split_whitespace();
-        match split.next() {
-            Some("$") => match split.next() {
-                Some("ls") => match split.next() {
-                    None => Ok(List),
-                    _ => Err(BadLs),
-                }
-                // this would work better with feature(str_split_whitespace_as_str)
-                Some("cd") => Ok(ChangeDir(s.strip_prefix("$ cd ").unwrap().parse()?)),
-                _ => Err(BadCommand),
-            }
-            Some(_) => Ok(Self::FsNode(s.parse()?)),
-            None => Err(BadCommand)
-        }
+        parse(command(), s)
     }
 }
 
@@ -212,25 +132,25 @@ impl Display for File {
     }
 }
 
-fn get_sizes(input: &str) -> (Vec<usize>, usize) {
-    let commands: Vec<Command> = input.lines().map(|line| line.parse().unwrap()).collect();
+fn get_sizes(input: &str) -> Option<(Vec<usize>, usize)> {
+    let commands: Vec<Command> = parse_lines(input).map_err(|errors| eprintln!("{errors}")).ok()?;
 
     let mut dir = Directory::default();
     dir.process_command_stream(&mut commands.into_iter());
 
     let mut sizes = Vec::new();
     let size = dir.collect_dir_sizes(&mut sizes);
-    (sizes, size)
+    Some((sizes, size))
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
-    let (sizes, _) = get_sizes(input);
+    let (sizes, _) = get_sizes(input)?;
     Some(sizes.iter().map(|&size| if size <= 100_000 { size } else { 0 }).sum())
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
     let target_size = 40_000_000;
-    let (sizes, size) = get_sizes(input);
+    let (sizes, size) = get_sizes(input)?;
     let over = size - target_size;
     sizes.into_iter().filter(|&s| {
         s >= over
@@ -258,4 +178,14 @@ mod tests {
         let input = advent_of_code::read_file("examples", 7);
         assert_eq!(part_two(&input), Some(24933642));
     }
+
+    #[test]
+    fn parse_errors() {
+        let errors = parse_lines::<Command>("$ cd /\n$ ls\n12x a.txt\n$ rm a.txt\n").unwrap_err().0;
+        assert_eq!(errors.iter().map(|err| err.line).collect::<Vec<_>>(), vec![3, 4]);
+        assert_eq!((errors[0].column, errors[0].cause.as_str()), (3, "expected whitespace"));
+        assert_eq!((errors[1].column, errors[1].cause.as_str()), (3, "expected \"cd \" or \"ls\""));
+        assert_eq!("$ cd ..".parse(), Ok(ChangeDir(Up)));
+        assert_eq!("$ ls -l".parse::<Command>().unwrap_err().cause, "expected the end of the input");
+    }
 }
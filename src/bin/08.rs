@@ -1,99 +1,131 @@
 use std::collections::HashSet;
-
-#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
-struct Tree {
-    x: usize,
-    y: usize,
-    height: usize,
-}
-
-fn scan_trees<F>(mut cur_pos: (usize, usize), direction: (i8, i8),
-                 map: &Vec<Vec<Tree>>, mut tree_found: F) where F: FnMut(Tree) -> bool {
-    while cur_pos.0 < map[0].len() && cur_pos.1 < map.len() && {
-        let cur_tree = map[cur_pos.1][cur_pos.0];
-
-        let res = tree_found(cur_tree);
-
-        // increment cur pos
-        cur_pos = (cur_pos.0.wrapping_add_signed(direction.0 as isize),
-                   cur_pos.1.wrapping_add_signed(direction.1 as isize));
-
-        res
-    } {}
-}
-
-fn test_range(direction: (i8, i8), map: &Vec<Vec<Tree>>, set: &mut HashSet<Tree>) {
-    for row in match direction {
-        (_, 1) => 0..1,
-        (_, -1) => map.len() - 1..map.len(),
-        (_, _) => 0..map.len(),
-    } {
-        for col in match direction {
-            (1, _) => 0..1,
-            (-1, _) => map[0].len() - 1..map.len(),
-            (_, _) => 0..map[0].len(),
-        } {
-            let mut last_highest_tree_size = -1isize;
-            scan_trees((col, row), direction, map, |tree| {
-                if tree.height as isize > last_highest_tree_size {
-                    last_highest_tree_size = tree.height as isize;
-                    set.insert(tree);
-
-                    // trees cannot be higher than 9, so stop scanning if it is
-                    tree.height < 9
-                } else {
-                    true
+use std::iter;
+
+use advent_of_code::helpers::{geom::Direction, grid::Grid};
+
+/// walks from every tree on the edge opposite to `direction` into the forest, marking the trees
+/// that are higher than all trees before them.
+fn test_range(direction: Direction, map: &Grid<u32>, set: &mut HashSet<(usize, usize)>) {
+    let (width, height) = (map.width(), map.height());
+    let starts: Vec<(usize, usize)> = match direction {
+        Direction::East => (0..height).map(|y| (0, y)).collect(),
+        Direction::West => (0..height).map(|y| (width - 1, y)).collect(),
+        Direction::South => (0..width).map(|x| (x, 0)).collect(),
+        Direction::North => (0..width).map(|x| (x, height - 1)).collect(),
+        _ => unreachable!("trees are only seen along rows and columns"),
+    };
+
+    for start in starts {
+        let mut last_highest_tree_size = -1i64;
+        for (position, &tree) in iter::once((start, &map[start])).chain(map.ray(start, direction)) {
+            if i64::from(tree) > last_highest_tree_size {
+                last_highest_tree_size = i64::from(tree);
+                set.insert(position);
+
+                // trees cannot be higher than 9, so stop scanning if it is
+                if tree == 9 {
+                    break;
                 }
-            });
+            }
         }
     }
 }
 
-fn test_view(position: (usize, usize), map: &Vec<Vec<Tree>>) -> usize {
-    let res = vec![(1, 0), (-1, 0), (0, 1), (0, -1)].iter().map(|&dir| {
+fn test_view(position: (usize, usize), map: &Grid<u32>) -> usize {
+    Direction::CARDINAL.iter().map(|dir| {
         let mut visible = 0;
-        scan_trees(position, dir, map, |tree| {
+        for (_, &tree) in map.ray(position, *dir) {
             visible += 1;
-            // skip the first tree
-            visible == 1 || tree.height < map[position.1][position.0].height
-        });
-        visible - 1
-    }).product();
-    res
+            if tree >= map[position] {
+                break;
+            }
+        }
+        visible
+    }).product()
 }
 
-fn parse_map(input: &str) -> Vec<Vec<Tree>> {
-    input.lines().enumerate().map(|(y, line)| {
-        line.chars().enumerate().map(|(x, c)| {
-            Tree { x, y, height: c.to_string().parse().unwrap() }
-        }).collect()
-    }).collect()
+fn parse_map(input: &str) -> Grid<u32> {
+    Grid::parse_with(input, |c| c.to_digit(10)).unwrap()
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
     let map = parse_map(input);
-    let mut set: HashSet<Tree> = HashSet::new();
+    let mut set = HashSet::new();
 
-    for dir in vec![(1, 0), (-1, 0), (0, 1), (0, -1)] {
+    for dir in Direction::CARDINAL {
         test_range(dir, &map, &mut set)
     }
 
     Some(set.len() as u32)
 }
 
+/// checks every tree against all trees between it and each edge.
+pub fn part_one_brute_force(input: &str) -> Option<u32> {
+    let map = parse_map(input);
+    let visible = |position| {
+        Direction::CARDINAL.iter().any(|dir| map.ray(position, *dir).all(|(_, &tree)| tree < map[position]))
+    };
+    Some(map.positions().filter(|&position| visible(position)).count() as u32)
+}
+
 pub fn part_two(input: &str) -> Option<u32> {
     let map = parse_map(input);
-    Some(map.iter().enumerate().map(|(y, row)| {
-        row.iter().enumerate().map(|(x, tree)| {
-            test_view((x, y), &map)
-        }).max().unwrap()
-    }).max().unwrap() as u32)
+    map.positions().map(|position| test_view(position, &map)).max().map(|score| score as u32)
+}
+
+/// multiplies every tree's viewing distance along each line into `scores`. a monotonic stack of
+/// the trees seen so far finds the closest tree that blocks the view in amortised constant time.
+fn view_along(line: &[(usize, usize)], map: &Grid<u32>, scores: &mut Grid<usize>) {
+    let mut stack: Vec<usize> = Vec::new();
+    for (i, &position) in line.iter().enumerate() {
+        let height = map[position];
+        while stack.last().is_some_and(|&j| map[line[j]] < height) {
+            stack.pop();
+        }
+        scores[position] *= match stack.last() {
+            Some(&j) => i - j,
+            None => i,
+        };
+        stack.push(i);
+    }
+}
+
+pub fn part_two_stack(input: &str) -> Option<u32> {
+    let map = parse_map(input);
+    let (width, height) = (map.width(), map.height());
+    let mut scores = Grid::new(width, height, 1);
+
+    for y in 0..height {
+        let mut row: Vec<_> = (0..width).map(|x| (x, y)).collect();
+        view_along(&row, &map, &mut scores);
+        row.reverse();
+        view_along(&row, &map, &mut scores);
+    }
+    for x in 0..width {
+        let mut col: Vec<_> = (0..height).map(|y| (x, y)).collect();
+        view_along(&col, &map, &mut scores);
+        col.reverse();
+        view_along(&col, &map, &mut scores);
+    }
+
+    scores.iter().map(|(_, &score)| score).max().map(|score| score as u32)
+}
+
+/// a forest of random size and shape, for `cargo fuzz-diff 08`.
+fn generate_input(rng: &mut advent_of_code::fuzz::Rng) -> String {
+    let (width, height) = (rng.usize(1..=8), rng.usize(1..=8));
+    let max_height = rng.range(0..=9);
+    (0..height).map(|_| {
+        let row: String = (0..width).map(|_| char::from(b'0' + rng.range(0..=max_height) as u8)).collect();
+        row + "\n"
+    }).collect()
 }
 
 fn main() {
+    advent_of_code::fuzz_diff!(generate_input, 1 => [part_one, part_one_brute_force], 2 => [part_two, part_two_stack]);
     let input = &advent_of_code::read_file("inputs", 8);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::solve!(1, input, [part_one, part_one_brute_force]);
+    advent_of_code::solve!(2, input, [part_two, part_two_stack]);
 }
 
 #[cfg(test)]
@@ -104,11 +136,21 @@ mod tests {
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 8);
         assert_eq!(part_one(&input), Some(21));
+        assert_eq!(part_one_brute_force(&input), Some(21));
+    }
+
+    #[test]
+    fn test_part_one_wide_grid() {
+        // found by `cargo fuzz-diff 08`: scanning from the right missed columns past the height.
+        let input = "22322\n53212\n12423\n";
+        assert_eq!(part_one(input), Some(13));
+        assert_eq!(part_one_brute_force(input), Some(13));
     }
 
     #[test]
     fn test_part_two() {
         let input = advent_of_code::read_file("examples", 8);
         assert_eq!(part_two(&input), Some(8));
+        assert_eq!(part_two_stack(&input), Some(8));
     }
 }
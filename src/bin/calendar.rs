@@ -0,0 +1,95 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::{
+    answers::Answers,
+    calendar::{self, DayStatus, Tests},
+    clock::{self, Clock, SystemClock},
+    config, history, scaffold,
+    timings::Timings,
+};
+use std::process::{self, Command, Stdio};
+
+struct Args {
+    no_tests: bool,
+    overrides: config::Overrides,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        no_tests: args.contains("--no-tests"),
+        overrides: config::Overrides::from_args(&mut args)?,
+    })
+}
+
+/// runs the unit tests of a day, which check the solution against the example.
+fn run_tests(day: u8) -> Tests {
+    let status = Command::new("cargo")
+        .args(["test", "--quiet", "--bin", &format!("{:02}", day)])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+    match status {
+        Ok(status) if status.success() => Tests::Passed,
+        Ok(_) => Tests::Failed,
+        Err(_) => Tests::NotRun,
+    }
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let config = match config::init(&args.overrides) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Failed to load {}: {}", config::CONFIG_FILE, e);
+            process::exit(1);
+        }
+    };
+
+    let now = SystemClock.now();
+    let history = history::load(&config.paths.history).unwrap_or_else(|e| {
+        eprintln!(
+            "Could not read \"{}\": {}",
+            config.paths.history.display(),
+            e
+        );
+        Vec::new()
+    });
+    let timings = Timings::from_history(&history);
+    if !args.no_tests {
+        println!("Running example tests, pass --no-tests to skip them...");
+    }
+
+    let days = (1..=25)
+        .map(|day| {
+            let scaffolded = scaffold::module_path(day).exists();
+            let answers = Answers::load(day);
+            DayStatus {
+                day,
+                unlocked: config
+                    .year
+                    .is_none_or(|year| clock::unlock_time(year, day) <= now),
+                scaffolded,
+                solved: [answers.part_one.is_some(), answers.part_two.is_some()],
+                tests: if scaffolded && !args.no_tests {
+                    run_tests(day)
+                } else {
+                    Tests::NotRun
+                },
+                time: timings.total(day),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    println!();
+    print!("{}", calendar::render(config.year, &days, config.output));
+}
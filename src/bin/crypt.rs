@@ -0,0 +1,154 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::{
+    config::{self, Config},
+    crypt::{self, CryptError, Key},
+};
+use std::{fs, path::Path, process};
+
+enum Action {
+    Encrypt,
+    Decrypt { force: bool },
+    Rekey { new_key_env: Option<String> },
+}
+
+struct Args {
+    action: Action,
+    overrides: config::Overrides,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    let force = args.contains(["-f", "--force"]);
+    let new_key_env = args.opt_value_from_str("--new-key-env")?;
+    let overrides = config::Overrides::from_args(&mut args)?;
+    let action = match args.free_from_str::<String>()?.as_str() {
+        "encrypt" => Action::Encrypt,
+        "decrypt" => Action::Decrypt { force },
+        "rekey" => Action::Rekey { new_key_env },
+        other => {
+            return Err(pico_args::Error::ArgumentParsingFailed {
+                cause: format!("unknown command \"{}\"", other),
+            })
+        }
+    };
+    Ok(Args { action, overrides })
+}
+
+fn io_error(path: &Path, e: std::io::Error) -> CryptError {
+    CryptError::Io(path.to_path_buf(), e.to_string())
+}
+
+fn encrypt(config: &Config, key: &Key) -> Result<(), CryptError> {
+    for dir in [&config.paths.inputs, &config.paths.answers] {
+        for path in crypt::plain_files(dir)? {
+            let plaintext = fs::read(&path).map_err(|e| io_error(&path, e))?;
+            if plaintext.is_empty() {
+                continue;
+            }
+
+            // ciphertexts differ on every run, so only rewrite files whose contents changed.
+            let encrypted_path = crypt::encrypted_path(&path);
+            let unchanged = fs::read(&encrypted_path)
+                .ok()
+                .and_then(|data| key.decrypt(&data).ok())
+                .is_some_and(|existing| existing == plaintext);
+            if unchanged {
+                continue;
+            }
+
+            fs::write(&encrypted_path, key.encrypt(&plaintext))
+                .map_err(|e| io_error(&encrypted_path, e))?;
+            println!("Encrypted \"{}\"", path.display());
+        }
+    }
+    Ok(())
+}
+
+fn decrypt(config: &Config, key: &Key, force: bool) -> Result<(), CryptError> {
+    for dir in [&config.paths.inputs, &config.paths.answers] {
+        for encrypted_path in crypt::encrypted_files(dir)? {
+            let path = encrypted_path.with_extension("");
+            let data = fs::read(&encrypted_path).map_err(|e| io_error(&encrypted_path, e))?;
+            let plaintext = key.decrypt(&data)?;
+
+            match fs::read(&path) {
+                Ok(existing) if existing == plaintext => continue,
+                Ok(existing) if !existing.is_empty() && !force => {
+                    println!(
+                        "Skipped \"{}\": it differs from the encrypted file. Use --force to overwrite it.",
+                        path.display()
+                    );
+                    continue;
+                }
+                _ => {}
+            }
+
+            fs::write(&path, plaintext).map_err(|e| io_error(&path, e))?;
+            println!("Decrypted \"{}\"", path.display());
+        }
+    }
+    Ok(())
+}
+
+fn rekey(config: &Config, old_key: &Key, new_key: &Key) -> Result<(), CryptError> {
+    // decrypt everything before writing anything, so a wrong key leaves the files untouched.
+    let mut files = Vec::new();
+    for dir in [&config.paths.inputs, &config.paths.answers] {
+        for encrypted_path in crypt::encrypted_files(dir)? {
+            let data = fs::read(&encrypted_path).map_err(|e| io_error(&encrypted_path, e))?;
+            files.push((old_key.decrypt(&data)?, encrypted_path));
+        }
+    }
+
+    for (plaintext, encrypted_path) in files {
+        fs::write(&encrypted_path, new_key.encrypt(&plaintext))
+            .map_err(|e| io_error(&encrypted_path, e))?;
+        println!("Re-encrypted \"{}\"", encrypted_path.display());
+    }
+    Ok(())
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            eprintln!("Usage: cargo crypt <encrypt|decrypt|rekey> [--force] [--new-key-env VAR]");
+            process::exit(1);
+        }
+    };
+
+    let config = match config::init(&args.overrides) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Failed to load {}: {}", config::CONFIG_FILE, e);
+            process::exit(1);
+        }
+    };
+
+    let result = Key::from_env(&config.encryption.key_env).and_then(|key| match args.action {
+        Action::Encrypt => encrypt(config, &key),
+        Action::Decrypt { force } => decrypt(config, &key, force),
+        Action::Rekey { new_key_env } => {
+            let new_key_env =
+                new_key_env.unwrap_or_else(|| format!("{}_NEW", config.encryption.key_env));
+            Key::from_env(&new_key_env).and_then(|new_key| rekey(config, &key, &new_key))
+        }
+    });
+
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+
+    if !config.encryption.enabled {
+        println!("---");
+        println!(
+            "🎄 Set `enabled = true` in the [encryption] section of {} to read encrypted files.",
+            config::CONFIG_FILE
+        );
+    }
+}
@@ -2,19 +2,21 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
-use advent_of_code::aoc_cli;
+use advent_of_code::{aoc_cli, config};
 use std::process;
 
 struct Args {
     day: u8,
-    year: Option<u16>,
+    force: bool,
+    overrides: config::Overrides,
 }
 
 fn parse_args() -> Result<Args, pico_args::Error> {
     let mut args = pico_args::Arguments::from_env();
     Ok(Args {
+        force: args.contains(["-f", "--force"]),
+        overrides: config::Overrides::from_args(&mut args)?,
         day: args.free_from_str()?,
-        year: args.opt_value_from_str(["-y", "--year"])?,
     })
 }
 
@@ -27,19 +29,27 @@ fn main() {
         }
     };
 
+    let config = match config::init(&args.overrides) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Failed to load {}: {}", config::CONFIG_FILE, e);
+            process::exit(1);
+        }
+    };
+
     if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+        eprintln!(
+            "command \"{}\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.",
+            config.aoc_executable
+        );
         process::exit(1);
     }
 
-    match aoc_cli::download(args.day, args.year) {
-        Ok(cmd_output) => {
-            if !cmd_output.status.success() {
-                process::exit(1);
-            }
-        }
+    match aoc_cli::download(args.day, config.year, args.force) {
+        Ok(_) => {}
+        Err(aoc_cli::AocCliError::BadExitStatus(_)) => process::exit(1),
         Err(e) => {
-            eprintln!("failed to spawn aoc-cli: {}", e);
+            eprintln!("failed to download input: {}", e);
             process::exit(1);
         }
     }
@@ -0,0 +1,84 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::{config, scaffold};
+use std::{
+    fs,
+    process::{self, Command},
+};
+
+struct Args {
+    day: u8,
+    runs: Option<u64>,
+    seed: Option<u64>,
+    overrides: config::Overrides,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        runs: args.opt_value_from_str(["-r", "--runs"])?,
+        seed: args.opt_value_from_str(["-s", "--seed"])?,
+        overrides: config::Overrides::from_args(&mut args)?,
+        day: args.free_from_str()?,
+    })
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(_) => {
+            eprintln!("Need to specify a day (as integer). example: `cargo fuzz-diff 8`");
+            process::exit(1);
+        }
+    };
+
+    if let Err(e) = config::init(&args.overrides) {
+        eprintln!("Failed to load {}: {}", config::CONFIG_FILE, e);
+        process::exit(1);
+    }
+
+    let day_padded = format!("{:02}", args.day);
+    let module_path = scaffold::module_path(args.day);
+    match fs::read_to_string(&module_path) {
+        Ok(module) if module.contains("fuzz_diff!") => {}
+        Ok(_) => {
+            eprintln!(
+                "Day {} has no input generator. Add `advent_of_code::fuzz_diff!(generate_input, 1 => [part_one, ...])` to the start of `main` in \"{}\".",
+                args.day,
+                module_path.display()
+            );
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Could not read \"{}\": {}", module_path.display(), e);
+            process::exit(1);
+        }
+    }
+
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--quiet".into(),
+        "--release".into(),
+        "--bin".into(),
+        day_padded,
+        "--".into(),
+        "--fuzz-diff".into(),
+    ];
+    if let Some(runs) = args.runs {
+        cmd_args.extend(["--runs".into(), runs.to_string()]);
+    }
+    if let Some(seed) = args.seed {
+        cmd_args.extend(["--seed".into(), seed.to_string()]);
+    }
+    cmd_args.extend(args.overrides.to_args());
+
+    match Command::new("cargo").args(&cmd_args).status() {
+        Ok(status) => process::exit(status.code().unwrap_or(1)),
+        Err(e) => {
+            eprintln!("Failed to run cargo: {}", e);
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,66 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::{config, history};
+use std::process;
+
+struct Args {
+    day: u8,
+    overrides: config::Overrides,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        overrides: config::Overrides::from_args(&mut args)?,
+        day: args.free_from_str()?,
+    })
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(_) => {
+            eprintln!("Need to specify a day (as integer). example: `cargo history 7`");
+            process::exit(1);
+        }
+    };
+
+    let config = match config::init(&args.overrides) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Failed to load {}: {}", config::CONFIG_FILE, e);
+            process::exit(1);
+        }
+    };
+
+    let entries = match history::load(&config.paths.history) {
+        Ok(entries) => entries,
+        Err(e) => {
+            eprintln!(
+                "Could not read \"{}\": {}",
+                config.paths.history.display(),
+                e
+            );
+            process::exit(1);
+        }
+    };
+
+    let revisions = history::revisions(&entries, args.day);
+    if revisions.is_empty() {
+        println!(
+            "🎄 No runs of day {} recorded yet. Type `cargo solve {:02}` to add one.",
+            args.day, args.day
+        );
+        return;
+    }
+
+    println!(
+        "{}",
+        config
+            .output
+            .banner(&format!("Day {:02} history", args.day))
+    );
+    print!("{}", history::render(&revisions));
+}
@@ -0,0 +1,140 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::{config, leaderboard::Leaderboard};
+use std::{
+    env, fs,
+    io::Write,
+    path::{Path, PathBuf},
+    process::{self, Command, Stdio},
+};
+
+struct Args {
+    day: Option<u8>,
+    file: Option<PathBuf>,
+    fetch: bool,
+    id: Option<u64>,
+    overrides: config::Overrides,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        day: args.opt_value_from_str(["-d", "--day"])?,
+        file: args.opt_value_from_str("--file")?,
+        fetch: args.contains("--fetch"),
+        id: args.opt_value_from_str("--id")?,
+        overrides: config::Overrides::from_args(&mut args)?,
+    })
+}
+
+/// the session cookie, read from the same places as aoc-cli.
+fn session() -> Option<String> {
+    if let Ok(session) = env::var("ADVENT_OF_CODE_SESSION") {
+        return Some(session);
+    }
+    let home = env::var_os("HOME")?;
+    let session = fs::read_to_string(Path::new(&home).join(".adventofcode.session")).ok()?;
+    Some(session.trim().to_string())
+}
+
+fn fetch(client: &str, year: u16, id: u64, file: &Path) -> Result<(), String> {
+    let session = session().ok_or(
+        "no session cookie found. Set ADVENT_OF_CODE_SESSION or create ~/.adventofcode.session.",
+    )?;
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+
+    let url = format!(
+        "https://adventofcode.com/{}/leaderboard/private/view/{}.json",
+        year, id
+    );
+    // pass the cookie in a config on stdin, so it does not show up in the process list
+    let mut child = Command::new(client)
+        .args(["--fail", "--silent", "--show-error", "--config", "-"])
+        .arg("--output")
+        .arg(file)
+        .arg(&url)
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("could not call \"{}\": {}", client, e))?;
+    let cookie = format!(
+        "cookie = \"session={}\"\n",
+        session.replace('\\', "\\\\").replace('"', "\\\"")
+    );
+    let written = child.stdin.take().unwrap().write_all(cookie.as_bytes());
+    let status = child
+        .wait()
+        .map_err(|e| format!("could not call \"{}\": {}", client, e))?;
+    written.map_err(|e| format!("could not pass the session to \"{}\": {}", client, e))?;
+    if !status.success() {
+        return Err(format!("\"{}\" failed to fetch {}", client, url));
+    }
+    println!("🎄 Saved leaderboard to \"{}\".", file.display());
+    Ok(())
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let config = match config::init(&args.overrides) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Failed to load {}: {}", config::CONFIG_FILE, e);
+            process::exit(1);
+        }
+    };
+    let file = args.file.unwrap_or_else(|| config.leaderboard.file.clone());
+
+    if args.fetch {
+        let (year, id) = match (config.year, args.id.or(config.leaderboard.id)) {
+            (Some(year), Some(id)) => (year, id),
+            _ => {
+                eprintln!("Fetching needs a year and a leaderboard id. Set `year` and `leaderboard.id` in {} or pass --year and --id.", config::CONFIG_FILE);
+                process::exit(1);
+            }
+        };
+        if let Err(e) = fetch(&config.leaderboard.client, year, id, &file) {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    }
+
+    let text = match fs::read_to_string(&file) {
+        Ok(text) => text,
+        Err(e) => {
+            eprintln!(
+                "Could not read leaderboard \"{}\": {}. Save the JSON from the leaderboard page there or pass --fetch.",
+                file.display(),
+                e
+            );
+            process::exit(1);
+        }
+    };
+    let leaderboard = match Leaderboard::parse(&text) {
+        Ok(leaderboard) => leaderboard,
+        Err(e) => {
+            eprintln!("Failed to read \"{}\": {}", file.display(), e);
+            process::exit(1);
+        }
+    };
+
+    let output = config.output;
+    match args.day {
+        Some(day) => print!("{}", leaderboard.render_day(day)),
+        None => {
+            if let Some(event) = leaderboard.event {
+                println!("{}", output.banner(&format!("Leaderboard {}", event)));
+            }
+            print!("{}", leaderboard.render_rankings());
+        }
+    }
+}
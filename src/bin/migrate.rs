@@ -0,0 +1,82 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::{config, scaffold};
+use std::process;
+
+struct Args {
+    migration: String,
+    dry_run: bool,
+    overrides: config::Overrides,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        dry_run: args.contains(["-n", "--dry-run"]),
+        overrides: config::Overrides::from_args(&mut args)?,
+        migration: args.free_from_str()?,
+    })
+}
+
+/// creates the bench harness of every day that was scaffolded before benches existed.
+fn backfill_benches(dry_run: bool) -> Result<usize, scaffold::ScaffoldError> {
+    let mut created = 0;
+    for day in 1..=25 {
+        if !scaffold::module_path(day).exists() {
+            continue;
+        }
+        let step = scaffold::plan_bench(
+            &scaffold::bench_path(day),
+            scaffold::render_bench(day),
+            false,
+        );
+        if step.action == scaffold::Action::Create {
+            if !dry_run {
+                scaffold::execute(&step)?;
+            }
+            println!("{}", step.describe(dry_run));
+            created += 1;
+        }
+        scaffold::ensure_bench_registered(day, dry_run)?;
+    }
+    Ok(created)
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(_) => {
+            eprintln!("Need to specify a migration. example: `cargo migrate benches`");
+            process::exit(1);
+        }
+    };
+
+    if let Err(e) = config::init(&args.overrides) {
+        eprintln!("Failed to load {}: {}", config::CONFIG_FILE, e);
+        process::exit(1);
+    }
+
+    match args.migration.as_str() {
+        "benches" => match backfill_benches(args.dry_run) {
+            Ok(0) => println!("🎄 Every day already has a bench."),
+            Ok(_) if args.dry_run => {}
+            Ok(created) => {
+                println!("---");
+                println!(
+                    "🎄 Created {} bench(es). Type `cargo bench --bench NN` to run one.",
+                    created
+                );
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        },
+        migration => {
+            eprintln!("Unknown migration \"{}\". Available: benches", migration);
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,114 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::{
+    aoc_cli,
+    clock::{self, Clock, SystemClock},
+    config, scaffold,
+};
+use std::{
+    io::{self, Write},
+    process,
+};
+
+struct Args {
+    no_wait: bool,
+    overrides: config::Overrides,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        no_wait: args.contains("--no-wait"),
+        overrides: config::Overrides::from_args(&mut args)?,
+    })
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let config = match config::init(&args.overrides) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Failed to load {}: {}", config::CONFIG_FILE, e);
+            process::exit(1);
+        }
+    };
+
+    let clock = SystemClock;
+    let unlock = match clock::next_unlock(config.year, clock.now()) {
+        Some(unlock) => unlock,
+        None => {
+            println!(
+                "🎄 All puzzles of {} are unlocked.",
+                config.year.map_or("this event".into(), |y| y.to_string())
+            );
+            return;
+        }
+    };
+
+    println!(
+        "🎄 Day {} of {} unlocks at {}.",
+        unlock.day,
+        unlock.year,
+        clock::format_timestamp(unlock.at)
+    );
+
+    if args.no_wait {
+        return;
+    }
+
+    if aoc_cli::check().is_err() {
+        eprintln!(
+            "command \"{}\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.",
+            config.aoc_executable
+        );
+        process::exit(1);
+    }
+
+    clock::wait_until(&clock, unlock.at, |remaining| {
+        print!("\r⏳ {} ", clock::format_countdown(remaining));
+        io::stdout().flush().ok();
+    });
+    println!("\r🔓 Unlocked!          ");
+
+    let options = scaffold::Options {
+        year: Some(unlock.year),
+        ..scaffold::Options::default()
+    };
+    match scaffold::scaffold(&config.paths, unlock.day, &options) {
+        Ok(_) => {}
+        Err(scaffold::ScaffoldError::ModuleExists(path)) => {
+            println!(
+                "Module file \"{}\" already exists, skipping scaffold.",
+                path.display()
+            );
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    }
+
+    match aoc_cli::download(unlock.day, Some(unlock.year), false) {
+        Ok(_) => {}
+        Err(aoc_cli::AocCliError::BadExitStatus(_)) => process::exit(1),
+        Err(e) => {
+            eprintln!("failed to download input: {}", e);
+            process::exit(1);
+        }
+    }
+
+    println!("---");
+    println!(
+        "🎄 Type `cargo solve {:02}` to run your solution.",
+        unlock.day
+    );
+}
@@ -2,113 +2,75 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
-use std::{
-    fs::{File, OpenOptions},
-    io::Write,
-    process,
-};
-
-const MODULE_TEMPLATE: &str = r###"pub fn part_one(input: &str) -> Option<u32> {
-    None
-}
-
-pub fn part_two(input: &str) -> Option<u32> {
-    None
-}
-
-fn main() {
-    let input = &advent_of_code::read_file("inputs", DAY);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", DAY);
-        assert_eq!(part_one(&input), None);
-    }
-
-    #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", DAY);
-        assert_eq!(part_two(&input), None);
-    }
+use advent_of_code::{config, scaffold};
+use std::process;
+
+struct Args {
+    day: u8,
+    template: Option<String>,
+    answer: Option<String>,
+    answer_two: Option<String>,
+    dry_run: bool,
+    force: bool,
+    reset_data: bool,
+    overrides: config::Overrides,
 }
-"###;
 
-fn parse_args() -> Result<u8, pico_args::Error> {
+fn parse_args() -> Result<Args, pico_args::Error> {
     let mut args = pico_args::Arguments::from_env();
-    args.free_from_str()
-}
-
-fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create_new(true).open(path)
-}
-
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    Ok(Args {
+        template: args.opt_value_from_str(["-t", "--template"])?,
+        answer: args.opt_value_from_str(["-a", "--answer"])?,
+        answer_two: args.opt_value_from_str("--answer-two")?,
+        dry_run: args.contains(["-n", "--dry-run"]),
+        force: args.contains(["-f", "--force"]),
+        reset_data: args.contains("--reset-data"),
+        overrides: config::Overrides::from_args(&mut args)?,
+        day: args.free_from_str()?,
+    })
 }
 
 fn main() {
-    let day = match parse_args() {
-        Ok(day) => day,
+    let args = match parse_args() {
+        Ok(args) => args,
         Err(_) => {
             eprintln!("Need to specify a day (as integer). example: `cargo scaffold 7`");
             process::exit(1);
         }
     };
 
-    let day_padded = format!("{:02}", day);
-
-    let input_path = format!("src/inputs/{}.txt", day_padded);
-    let example_path = format!("src/examples/{}.txt", day_padded);
-    let module_path = format!("src/bin/{}.rs", day_padded);
-
-    let mut file = match safe_create_file(&module_path) {
-        Ok(file) => file,
+    let config = match config::init(&args.overrides) {
+        Ok(config) => config,
         Err(e) => {
-            eprintln!("Failed to create module file: {}", e);
+            eprintln!("Failed to load {}: {}", config::CONFIG_FILE, e);
             process::exit(1);
         }
     };
 
-    match file.write_all(MODULE_TEMPLATE.replace("DAY", &day.to_string()).as_bytes()) {
-        Ok(_) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {}", e);
-            process::exit(1);
-        }
-    }
+    let defaults = scaffold::Options::default();
+    let part_one_type = args.answer.unwrap_or(defaults.part_one_type);
+    let options = scaffold::Options {
+        template: args.template,
+        part_two_type: args.answer_two.unwrap_or_else(|| part_one_type.clone()),
+        part_one_type,
+        year: config.year,
+        dry_run: args.dry_run,
+        force: args.force,
+        reset_data: args.reset_data,
+    };
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {}", e);
-            process::exit(1);
-        }
+    if let Err(e) = scaffold::scaffold(&config.paths, args.day, &options) {
+        eprintln!("{}", e);
+        process::exit(1);
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {}", e);
-            process::exit(1);
-        }
+    if args.dry_run {
+        return;
     }
 
     println!("---");
     println!(
-        "🎄 Type `cargo solve {}` to run your solution.",
-        &day_padded
+        "🎄 Type `cargo solve {:02}` to run your solution.",
+        args.day
     );
 }
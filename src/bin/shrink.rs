@@ -0,0 +1,230 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::{
+    answers::Answers,
+    config, crypt, scaffold,
+    shrink::{self, Failure},
+};
+use std::{
+    fs,
+    io::Read,
+    path::PathBuf,
+    process::{self, Command, Stdio},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+struct Args {
+    day: u8,
+    part: Option<u8>,
+    input: Option<PathBuf>,
+    timeout: u64,
+    overrides: config::Overrides,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        part: args.opt_value_from_str(["-p", "--part"])?,
+        input: args.opt_value_from_str("--input")?,
+        timeout: args.opt_value_from_str("--timeout")?.unwrap_or(10),
+        overrides: config::Overrides::from_args(&mut args)?,
+        day: args.free_from_str()?,
+    })
+}
+
+/// runs a compiled day against inputs written to a scratch folder.
+struct Runner {
+    executable: PathBuf,
+    folder: PathBuf,
+    day: u8,
+    timeout: Duration,
+    runs: usize,
+}
+
+/// reads all of `pipe` on a thread, so that a child writing more than the pipe buffer does not
+/// block until it is killed.
+fn drain(mut pipe: impl Read + Send + 'static) -> JoinHandle<String> {
+    thread::spawn(move || {
+        let mut text = String::new();
+        pipe.read_to_string(&mut text).ok();
+        text
+    })
+}
+
+impl Runner {
+    /// runs `part` on `input`. with an `expected` answer, a different answer is a failure too.
+    fn run(&mut self, part: u8, input: &str, expected: Option<&str>) -> Option<Failure> {
+        self.runs += 1;
+        fs::write(self.folder.join(format!("{:02}.txt", self.day)), input)
+            .unwrap_or_else(|e| panic!("could not write to {}: {}", self.folder.display(), e));
+
+        let mut child = Command::new(&self.executable)
+            .arg("--inputs-dir")
+            .arg(&self.folder)
+            .args(["--shrink-check", &part.to_string()])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap_or_else(|e| panic!("could not run {}: {}", self.executable.display(), e));
+        let stdout = drain(child.stdout.take().unwrap());
+        let stderr = drain(child.stderr.take().unwrap());
+
+        let started = Instant::now();
+        let status = loop {
+            match child.try_wait() {
+                Ok(Some(status)) => break status,
+                Ok(None) if started.elapsed() > self.timeout => {
+                    child.kill().ok();
+                    child.wait().ok();
+                    return Some(Failure::Timeout);
+                }
+                Ok(None) => thread::sleep(Duration::from_millis(5)),
+                Err(e) => panic!("could not wait for {}: {}", self.executable.display(), e),
+            }
+        };
+        let stdout = stdout.join().unwrap_or_default();
+        let stderr = stderr.join().unwrap_or_default();
+        shrink::classify(status.code(), &stderr).or_else(|| {
+            let expected = expected?;
+            let actual = shrink::reported_answer(&stdout);
+            (actual.as_deref() != Some(expected)).then(|| Failure::WrongAnswer {
+                expected: expected.to_string(),
+                actual,
+            })
+        })
+    }
+}
+
+fn fail(message: impl std::fmt::Display) -> ! {
+    eprintln!("{}", message);
+    process::exit(1);
+}
+
+/// builds the day in release mode and returns the path of its binary.
+fn build(day: &str) -> PathBuf {
+    let output = Command::new("cargo")
+        .args(["build", "--quiet", "--release", "--bin", day])
+        .arg("--message-format=json-render-diagnostics")
+        .stderr(Stdio::inherit())
+        .output();
+    match output {
+        Ok(output) if output.status.success() => {
+            shrink::executable(&String::from_utf8_lossy(&output.stdout), day)
+                .unwrap_or_else(|| fail(format!("cargo did not report a binary for day {}.", day)))
+        }
+        _ => fail(format!("Failed to build day {}.", day)),
+    }
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(_) => fail("Need to specify a day (as integer). example: `cargo shrink 7`"),
+    };
+    let config = match config::init(&args.overrides) {
+        Ok(config) => config,
+        Err(e) => fail(format!("Failed to load {}: {}", config::CONFIG_FILE, e)),
+    };
+
+    let input_path = args.input.unwrap_or_else(|| config.paths.input(args.day));
+    let input = match crypt::read(&input_path, &config.encryption) {
+        Ok(bytes) => String::from_utf8(bytes)
+            .unwrap_or_else(|_| fail(format!("\"{}\" is not valid UTF-8.", input_path.display()))),
+        Err(e) => fail(format!("Could not read input: {}", e)),
+    };
+
+    let executable = build(&format!("{:02}", args.day));
+    // a scratch folder next to the build profiles, e.g. `target/shrink`
+    let folder = executable
+        .parent()
+        .and_then(|profile| profile.parent())
+        .map_or_else(|| PathBuf::from("target"), PathBuf::from)
+        .join("shrink");
+    if let Err(e) = fs::create_dir_all(&folder) {
+        fail(format!("Could not create \"{}\": {}", folder.display(), e));
+    }
+    let mut runner = Runner {
+        executable,
+        folder,
+        day: args.day,
+        timeout: Duration::from_secs(args.timeout),
+        runs: 0,
+    };
+
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+    // the known answers only hold for the real input
+    let answers = if input_path == config.paths.input(args.day) {
+        Answers::load(args.day)
+    } else {
+        Answers::default()
+    };
+    let (part, failure) = match parts
+        .into_iter()
+        .find_map(|part| Some((part, runner.run(part, &input, answers.get(part))?)))
+    {
+        Some(found) => found,
+        None => fail(format!(
+            "Day {} does not fail on \"{}\". Nothing to shrink.",
+            args.day,
+            input_path.display()
+        )),
+    };
+    println!("Part {} fails: {}", part, failure);
+    if let Failure::WrongAnswer { .. } = failure {
+        fail(
+            "Smaller inputs have no known answer to compare with, so a wrong answer cannot be shrunk. \
+             Register a second implementation in `solve!` to shrink a disagreement instead.",
+        );
+    }
+    println!("Shrinking {} lines...", input.lines().count());
+
+    let shrunk = shrink::shrink(&input, |candidate| {
+        runner.run(part, candidate, None).as_ref() == Some(&failure)
+    });
+    println!(
+        "Shrunk to {} lines in {} runs.",
+        shrunk.lines().count(),
+        runner.runs
+    );
+
+    let (regression_path, number) = shrink::regression_path(&config.paths.examples, args.day);
+    if let Err(e) = fs::write(&regression_path, &shrunk) {
+        fail(format!(
+            "Could not write \"{}\": {}",
+            regression_path.display(),
+            e
+        ));
+    }
+    println!("Wrote reproducer to \"{}\".", regression_path.display());
+
+    let module_path = scaffold::module_path(args.day);
+    let test = shrink::regression_test(args.day, part, number, &failure);
+    let module = fs::read_to_string(&module_path)
+        .ok()
+        .and_then(|module| shrink::insert_test(&module, &test));
+    match module.map(|module| fs::write(&module_path, module)) {
+        Some(Ok(())) => println!(
+            "Added test_regression_{} to \"{}\". {}",
+            number,
+            module_path.display(),
+            if failure == Failure::Timeout {
+                "It is ignored so it cannot hang your tests; run it with `--ignored` once fixed."
+            } else {
+                "It fails until the bug is fixed."
+            }
+        ),
+        _ => {
+            println!(
+                "Could not add a test to \"{}\". Add it to its tests yourself:",
+                module_path.display()
+            );
+            print!("{}", test);
+        }
+    }
+}
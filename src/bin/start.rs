@@ -0,0 +1,166 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::{aoc_cli, config, puzzle, scaffold};
+use std::{fs, process};
+
+struct Args {
+    day: u8,
+    template: Option<String>,
+    answer: Option<String>,
+    answer_two: Option<String>,
+    overrides: config::Overrides,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        template: args.opt_value_from_str(["-t", "--template"])?,
+        answer: args.opt_value_from_str(["-a", "--answer"])?,
+        answer_two: args.opt_value_from_str("--answer-two")?,
+        overrides: config::Overrides::from_args(&mut args)?,
+        day: args.free_from_str()?,
+    })
+}
+
+const STEPS: usize = 4;
+
+/// prints the header of a step.
+fn step(number: usize, name: &str) {
+    println!("[{}/{}] {}", number, STEPS, name);
+}
+
+/// reports a failed step and exits without running the remaining ones.
+fn fail(number: usize, message: impl std::fmt::Display) -> ! {
+    eprintln!("{}", message);
+    eprintln!(
+        "Stopped at step {} of {}. Fix the problem and run `cargo start` again, finished steps are skipped.",
+        number, STEPS
+    );
+    process::exit(1);
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(_) => {
+            eprintln!("Need to specify a day (as integer). example: `cargo start 7`");
+            process::exit(1);
+        }
+    };
+
+    let config = match config::init(&args.overrides) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Failed to load {}: {}", config::CONFIG_FILE, e);
+            process::exit(1);
+        }
+    };
+    let day = args.day;
+
+    step(1, "Scaffolding");
+    let defaults = scaffold::Options::default();
+    let part_one_type = args.answer.unwrap_or(defaults.part_one_type);
+    let options = scaffold::Options {
+        template: args.template,
+        part_two_type: args.answer_two.unwrap_or_else(|| part_one_type.clone()),
+        part_one_type,
+        year: config.year,
+        ..scaffold::Options::default()
+    };
+    match scaffold::scaffold(&config.paths, day, &options) {
+        Ok(_) => {}
+        Err(scaffold::ScaffoldError::ModuleExists(path)) => {
+            println!(
+                "Module file \"{}\" already exists, keeping it.",
+                path.display()
+            );
+        }
+        Err(e) => fail(1, e),
+    }
+
+    step(2, "Downloading input and puzzle");
+    if aoc_cli::check().is_err() {
+        fail(
+            2,
+            format!(
+                "command \"{}\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.",
+                config.aoc_executable
+            ),
+        );
+    }
+    match aoc_cli::download(day, config.year, false) {
+        Ok(_) => {}
+        Err(e) => fail(2, format!("failed to download input: {}", e)),
+    }
+
+    step(3, "Extracting example");
+    let puzzle_path = config.paths.puzzle(day);
+    let markdown = match fs::read_to_string(&puzzle_path) {
+        Ok(markdown) => markdown,
+        Err(e) => fail(
+            3,
+            format!("could not read puzzle \"{}\": {}", puzzle_path.display(), e),
+        ),
+    };
+    let example_path = config.paths.example(day);
+    let has_example = fs::metadata(&example_path).is_ok_and(|m| m.len() > 0);
+    if has_example {
+        println!(
+            "Example file \"{}\" already has contents, keeping it.",
+            example_path.display()
+        );
+    } else {
+        match puzzle::example(&markdown) {
+            Some(example) => match fs::write(&example_path, example) {
+                Ok(_) => println!("Wrote example to \"{}\"", example_path.display()),
+                Err(e) => fail(
+                    3,
+                    format!("could not write \"{}\": {}", example_path.display(), e),
+                ),
+            },
+            None => fail(
+                3,
+                format!(
+                    "no code block found in \"{}\", add the example by hand.",
+                    puzzle_path.display()
+                ),
+            ),
+        }
+    }
+
+    step(4, "Generating tests");
+    let module_path = scaffold::module_path(day);
+    let module = match fs::read_to_string(&module_path) {
+        Ok(module) => module,
+        Err(e) => fail(
+            4,
+            format!("could not read \"{}\": {}", module_path.display(), e),
+        ),
+    };
+    let answers = puzzle::example_answers(&markdown);
+    let (module, filled) = puzzle::fill_assertions(&module, &answers, &options);
+    if let Err(e) = fs::write(&module_path, module) {
+        fail(
+            4,
+            format!("could not write \"{}\": {}", module_path.display(), e),
+        );
+    }
+    for (part, answer_type) in [(1, &options.part_one_type), (2, &options.part_two_type)] {
+        match answers.get(part) {
+            Some(answer) if filled.contains(&part) => {
+                println!("Part {}: expecting {} for the example", part, answer)
+            }
+            Some(answer) if puzzle::answer_literal(answer, answer_type).is_none() => println!(
+                "Part {}: the example answer {} is not a {}, add the assertion by hand.",
+                part, answer, answer_type
+            ),
+            Some(_) => println!("Part {}: test already has an assertion, keeping it.", part),
+            None => println!("Part {}: no example answer found in the puzzle.", part),
+        }
+    }
+
+    println!("---");
+    println!("🎄 Type `cargo solve {:02}` to run your solution.", day);
+}
@@ -0,0 +1,74 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::{
+    clock, config,
+    manifest::{self, Manifest, Status},
+};
+use std::process;
+
+fn main() {
+    let mut args = pico_args::Arguments::from_env();
+    let overrides = match config::Overrides::from_args(&mut args) {
+        Ok(overrides) => overrides,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let config = match config::init(&overrides) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Failed to load {}: {}", config::CONFIG_FILE, e);
+            process::exit(1);
+        }
+    };
+
+    let manifest_path = Manifest::path(&config.paths);
+    let results = Manifest::load(&manifest_path).and_then(|manifest| {
+        manifest::verify(&manifest, &config.paths.inputs, &config.encryption)
+            .map(|results| (manifest, results))
+    });
+    let (manifest, results) = match results {
+        Ok(results) => results,
+        Err(e) => {
+            eprintln!("Failed to verify inputs: {}", e);
+            process::exit(1);
+        }
+    };
+
+    if results.is_empty() {
+        println!("No downloaded inputs found.");
+        return;
+    }
+
+    let mut changed = false;
+    for (day, status) in results {
+        match (status, manifest.get(day)) {
+            (Status::Unchanged, _) => println!("✔ Day {:02}: unchanged", day),
+            (Status::Modified(size), Some(entry)) => {
+                changed = true;
+                println!(
+                    "✘ Day {:02}: modified since download at {} ({} → {} bytes)",
+                    day,
+                    clock::format_timestamp(entry.fetched_at),
+                    entry.size,
+                    size
+                );
+            }
+            (Status::Missing, _) => {
+                changed = true;
+                println!("✘ Day {:02}: missing", day);
+            }
+            (Status::Untracked, _) | (Status::Modified(_), None) => {
+                println!("? Day {:02}: not downloaded by `cargo download`", day)
+            }
+        }
+    }
+
+    if changed {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,138 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::time::Duration;
+
+use crate::config::OutputFormat;
+
+const COLUMNS: usize = 5;
+const CELL_WIDTH: usize = 17;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Tests {
+    Passed,
+    Failed,
+    NotRun,
+}
+
+/// everything the calendar shows about one day.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct DayStatus {
+    pub day: u8,
+    pub unlocked: bool,
+    pub scaffolded: bool,
+    /// whether a known answer is recorded for part one and part two.
+    pub solved: [bool; 2],
+    pub tests: Tests,
+    /// last measured time of both parts together.
+    pub time: Option<Duration>,
+}
+
+impl DayStatus {
+    fn cell(&self) -> String {
+        if !self.unlocked {
+            return format!("{:02} locked", self.day);
+        }
+        if !self.scaffolded {
+            return format!("{:02} ·", self.day);
+        }
+        let stars = self
+            .solved
+            .iter()
+            .map(|&solved| if solved { '★' } else { '☆' })
+            .collect::<String>();
+        let tests = match self.tests {
+            Tests::Passed => '✔',
+            Tests::Failed => '✘',
+            Tests::NotRun => ' ',
+        };
+        let time = self.time.map_or(String::new(), |t| format!("{:.2?}", t));
+        format!("{:02} {} {} {:>9}", self.day, stars, tests, time)
+    }
+}
+
+/// pads by characters rather than bytes, so that stars and check marks line up.
+fn pad(cell: &str) -> String {
+    let width = cell.chars().count();
+    format!("{}{}", cell, " ".repeat(CELL_WIDTH.saturating_sub(width)))
+}
+
+/// renders the days as a grid of five columns, followed by a legend.
+pub fn render(year: Option<u16>, days: &[DayStatus], output: OutputFormat) -> String {
+    let title = match year {
+        Some(year) => format!("Advent of Code {}", year),
+        None => "Advent of Code".into(),
+    };
+    let mut out = format!("{}{}{}\n\n", output.bold(), title, output.reset());
+
+    for row in days.chunks(COLUMNS) {
+        let cells = row.iter().map(|day| pad(&day.cell())).collect::<Vec<_>>();
+        out.push_str(cells.join(" │ ").trim_end());
+        out.push('\n');
+    }
+
+    let stars = days
+        .iter()
+        .map(|day| day.solved.iter().filter(|&&solved| solved).count())
+        .sum::<usize>();
+    out.push_str(&format!(
+        "\n{}★ {} stars with a known answer · ✔/✘ example tests · last measured time{}\n",
+        output.italic(),
+        stars,
+        output.reset()
+    ));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(day: u8) -> DayStatus {
+        DayStatus {
+            day,
+            unlocked: true,
+            scaffolded: false,
+            solved: [false, false],
+            tests: Tests::NotRun,
+            time: None,
+        }
+    }
+
+    #[test]
+    fn cells() {
+        let solved = DayStatus {
+            scaffolded: true,
+            solved: [true, false],
+            tests: Tests::Passed,
+            time: Some(Duration::from_micros(1500)),
+            ..status(1)
+        };
+        assert_eq!(solved.cell(), "01 ★☆ ✔    1.50ms");
+        assert_eq!(status(2).cell(), "02 ·");
+        assert_eq!(
+            DayStatus {
+                unlocked: false,
+                ..status(25)
+            }
+            .cell(),
+            "25 locked"
+        );
+    }
+
+    #[test]
+    fn grid() {
+        let days = (1..=7).map(status).collect::<Vec<_>>();
+        let rendered = render(Some(2022), &days, OutputFormat::Plain);
+        let lines = rendered.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines[0], "Advent of Code 2022");
+        assert_eq!(
+            lines[2],
+            format!("01 ·{0}│ 02 ·{0}│ 03 ·{0}│ 04 ·{0}│ 05 ·", " ".repeat(14))
+        );
+        assert_eq!(lines[3], format!("06 ·{}│ 07 ·", " ".repeat(14)));
+        assert!(lines[5].contains("0 stars"));
+    }
+}
@@ -0,0 +1,226 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::{
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// puzzles unlock at midnight US Eastern. December is always outside daylight saving time,
+/// so this is a fixed UTC-5 offset.
+const UNLOCK_HOUR_UTC: u64 = 5;
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// source of the current time, so that code waiting on puzzle unlocks can be tested.
+pub trait Clock {
+    /// seconds since the unix epoch.
+    fn now(&self) -> u64;
+
+    fn sleep(&self, duration: Duration);
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system clock is before the unix epoch")
+            .as_secs()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/// days since the unix epoch for a proleptic gregorian date.
+/// see: http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month = month as i64;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// inverse of `days_from_civil`, returns `(year, month, day)`.
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// formats a unix timestamp as `YYYY-MM-DD HH:MM:SS UTC`.
+pub fn format_timestamp(timestamp: u64) -> String {
+    let (year, month, day) = civil_from_days((timestamp / SECONDS_PER_DAY) as i64);
+    let seconds = timestamp % SECONDS_PER_DAY;
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+/// formats a number of seconds as e.g. `3d 04:05:06`.
+pub fn format_countdown(seconds: u64) -> String {
+    let days = seconds / SECONDS_PER_DAY;
+    let seconds = seconds % SECONDS_PER_DAY;
+    let time = format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    );
+    if days > 0 {
+        format!("{}d {}", days, time)
+    } else {
+        time
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Unlock {
+    pub year: u16,
+    pub day: u8,
+    /// unix timestamp of the unlock.
+    pub at: u64,
+}
+
+/// unix timestamp at which the puzzle for `day` of `year` unlocks.
+pub fn unlock_time(year: u16, day: u8) -> u64 {
+    days_from_civil(year as i64, 12, day as u32) as u64 * SECONDS_PER_DAY + UNLOCK_HOUR_UTC * 3600
+}
+
+/// finds the first puzzle of `year` that unlocks after `now`. without a year, the event of the
+/// current year is used, rolling over to next year's once it is over.
+pub fn next_unlock(year: Option<u16>, now: u64) -> Option<Unlock> {
+    let find = |year: u16| {
+        (1..=25)
+            .map(|day| Unlock {
+                year,
+                day,
+                at: unlock_time(year, day),
+            })
+            .find(|unlock| unlock.at > now)
+    };
+
+    match year {
+        Some(year) => find(year),
+        None => {
+            let (current_year, _, _) = civil_from_days((now / SECONDS_PER_DAY) as i64);
+            let current_year = current_year as u16;
+            find(current_year).or_else(|| find(current_year + 1))
+        }
+    }
+}
+
+/// sleeps until `at`, calling `on_tick` with the remaining seconds roughly once per second.
+pub fn wait_until(clock: &impl Clock, at: u64, mut on_tick: impl FnMut(u64)) {
+    loop {
+        let now = clock.now();
+        if now >= at {
+            return;
+        }
+        let remaining = at - now;
+        on_tick(remaining);
+        clock.sleep(Duration::from_secs(1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    struct FakeClock(Cell<u64>);
+
+    impl Clock for FakeClock {
+        fn now(&self) -> u64 {
+            self.0.get()
+        }
+
+        fn sleep(&self, duration: Duration) {
+            self.0.set(self.0.get() + duration.as_secs());
+        }
+    }
+
+    #[test]
+    fn civil_round_trip() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2022, 12, 1), 19327);
+        assert_eq!(civil_from_days(19327), (2022, 12, 1));
+        assert_eq!(civil_from_days(days_from_civil(2024, 2, 29)), (2024, 2, 29));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+    }
+
+    #[test]
+    fn unlock_is_midnight_eastern() {
+        // 2022-12-01T05:00:00Z
+        assert_eq!(unlock_time(2022, 1), 1669870800);
+        assert_eq!(
+            format_timestamp(unlock_time(2022, 25)),
+            "2022-12-25 05:00:00 UTC"
+        );
+    }
+
+    #[test]
+    fn next_unlock_with_year() {
+        let day_one = unlock_time(2022, 1);
+        assert_eq!(next_unlock(Some(2022), 0).map(|u| u.day), Some(1));
+        assert_eq!(next_unlock(Some(2022), day_one - 1).map(|u| u.day), Some(1));
+        assert_eq!(next_unlock(Some(2022), day_one).map(|u| u.day), Some(2));
+        assert_eq!(next_unlock(Some(2022), unlock_time(2022, 25)), None);
+    }
+
+    #[test]
+    fn next_unlock_rolls_over() {
+        let after_event = unlock_time(2022, 25) + 1;
+        assert_eq!(
+            next_unlock(None, after_event),
+            Some(Unlock {
+                year: 2023,
+                day: 1,
+                at: unlock_time(2023, 1)
+            })
+        );
+        let summer = days_from_civil(2023, 7, 1) as u64 * SECONDS_PER_DAY;
+        assert_eq!(
+            next_unlock(None, summer).map(|u| (u.year, u.day)),
+            Some((2023, 1))
+        );
+    }
+
+    #[test]
+    fn countdown() {
+        assert_eq!(format_countdown(59), "00:00:59");
+        assert_eq!(
+            format_countdown(3 * SECONDS_PER_DAY + 4 * 3600 + 5 * 60 + 6),
+            "3d 04:05:06"
+        );
+    }
+
+    #[test]
+    fn wait_until_ticks() {
+        let clock = FakeClock(Cell::new(100));
+        let mut ticks = Vec::new();
+        wait_until(&clock, 103, |remaining| ticks.push(remaining));
+        assert_eq!(ticks, vec![3, 2, 1]);
+        assert_eq!(clock.now(), 103);
+    }
+}
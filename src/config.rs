@@ -0,0 +1,698 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use crate::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+pub const CONFIG_FILE: &str = "aoc.toml";
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum OutputFormat {
+    #[default]
+    Pretty,
+    Plain,
+}
+
+impl OutputFormat {
+    pub fn bold(self) -> &'static str {
+        self.ansi(ANSI_BOLD)
+    }
+
+    pub fn italic(self) -> &'static str {
+        self.ansi(ANSI_ITALIC)
+    }
+
+    pub fn reset(self) -> &'static str {
+        self.ansi(ANSI_RESET)
+    }
+
+    /// wraps a heading in the festive decoration used by `solve!` and `cargo all`.
+    pub fn banner(self, text: &str) -> String {
+        match self {
+            OutputFormat::Pretty => format!("🎄 {}{}{} 🎄", ANSI_BOLD, text, ANSI_RESET),
+            OutputFormat::Plain => text.to_string(),
+        }
+    }
+
+    fn ansi(self, code: &'static str) -> &'static str {
+        match self {
+            OutputFormat::Pretty => code,
+            OutputFormat::Plain => "",
+        }
+    }
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pretty" => Ok(OutputFormat::Pretty),
+            "plain" => Ok(OutputFormat::Plain),
+            _ => Err(format!(
+                "unknown output format \"{}\", expected \"pretty\" or \"plain\"",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Paths {
+    pub inputs: PathBuf,
+    pub examples: PathBuf,
+    pub puzzles: PathBuf,
+    pub answers: PathBuf,
+    pub templates: PathBuf,
+    /// every answer and time printed by `solve!`. contains answers, so it is not committed.
+    pub history: PathBuf,
+}
+
+impl Default for Paths {
+    fn default() -> Self {
+        Paths {
+            inputs: PathBuf::from("src/inputs"),
+            examples: PathBuf::from("src/examples"),
+            puzzles: PathBuf::from("src/puzzles"),
+            answers: PathBuf::from("src/answers"),
+            templates: PathBuf::from("templates"),
+            history: PathBuf::from("history.tsv"),
+        }
+    }
+}
+
+impl Paths {
+    /// resolves one of the folder names used by `read_file` to its configured location.
+    pub fn folder(&self, name: &str) -> PathBuf {
+        match name {
+            "inputs" => self.inputs.clone(),
+            "examples" => self.examples.clone(),
+            "puzzles" => self.puzzles.clone(),
+            "answers" => self.answers.clone(),
+            _ => Path::new("src").join(name),
+        }
+    }
+
+    pub fn input(&self, day: u8) -> PathBuf {
+        self.inputs.join(format!("{:02}.txt", day))
+    }
+
+    pub fn example(&self, day: u8) -> PathBuf {
+        self.examples.join(format!("{:02}.txt", day))
+    }
+
+    pub fn puzzle(&self, day: u8) -> PathBuf {
+        self.puzzles.join(format!("{:02}.md", day))
+    }
+
+    pub fn answer(&self, day: u8) -> PathBuf {
+        self.answers.join(format!("{:02}.txt", day))
+    }
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Encryption {
+    /// whether `read_file` falls back to decrypting `.enc` files when the plain file is missing.
+    pub enabled: bool,
+    /// environment variable that holds the passphrase.
+    pub key_env: String,
+}
+
+impl Default for Encryption {
+    fn default() -> Self {
+        Encryption {
+            enabled: false,
+            key_env: "AOC_KEY".into(),
+        }
+    }
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Leaderboard {
+    /// id of the private leaderboard, which is the user id of its owner.
+    pub id: Option<u64>,
+    /// local copy of the leaderboard JSON.
+    pub file: PathBuf,
+    /// command used to fetch the leaderboard JSON, called like curl.
+    pub client: String,
+}
+
+impl Default for Leaderboard {
+    fn default() -> Self {
+        Leaderboard {
+            id: None,
+            file: PathBuf::from("target/leaderboard.json"),
+            client: "curl".into(),
+        }
+    }
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Config {
+    /// puzzle year. `None` lets aoc-cli pick the current event.
+    pub year: Option<u16>,
+    pub paths: Paths,
+    /// name or path of the aoc-cli executable.
+    pub aoc_executable: String,
+    /// whether `cargo all` builds solutions in release mode.
+    pub release: bool,
+    pub output: OutputFormat,
+    pub encryption: Encryption,
+    pub leaderboard: Leaderboard,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            year: None,
+            paths: Paths::default(),
+            aoc_executable: "aoc".into(),
+            release: cfg!(not(debug_assertions)),
+            output: OutputFormat::default(),
+            encryption: Encryption::default(),
+            leaderboard: Leaderboard::default(),
+        }
+    }
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum ConfigError {
+    Io(PathBuf, String),
+    Syntax { line: usize, message: String },
+    UnknownKey(String),
+    BadValue { key: String, message: String },
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Io(path, err) => write!(f, "could not read {}: {}", path.display(), err),
+            ConfigError::Syntax { line, message } => {
+                write!(f, "syntax error on line {}: {}", line, message)
+            }
+            ConfigError::UnknownKey(key) => write!(f, "unknown key \"{}\"", key),
+            ConfigError::BadValue { key, message } => {
+                write!(f, "bad value for \"{}\": {}", key, message)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+enum Value {
+    String(String),
+    Integer(i64),
+    Boolean(bool),
+}
+
+impl Value {
+    fn type_name(&self) -> &'static str {
+        match self {
+            Value::String(_) => "string",
+            Value::Integer(_) => "integer",
+            Value::Boolean(_) => "boolean",
+        }
+    }
+}
+
+fn bad_value(key: &str, message: impl Into<String>) -> ConfigError {
+    ConfigError::BadValue {
+        key: key.into(),
+        message: message.into(),
+    }
+}
+
+fn expect_string(key: &str, value: Value) -> Result<String, ConfigError> {
+    match value {
+        Value::String(s) => Ok(s),
+        other => Err(bad_value(
+            key,
+            format!("expected string, found {}", other.type_name()),
+        )),
+    }
+}
+
+fn expect_integer(key: &str, value: Value) -> Result<i64, ConfigError> {
+    match value {
+        Value::Integer(i) => Ok(i),
+        other => Err(bad_value(
+            key,
+            format!("expected integer, found {}", other.type_name()),
+        )),
+    }
+}
+
+fn expect_bool(key: &str, value: Value) -> Result<bool, ConfigError> {
+    match value {
+        Value::Boolean(b) => Ok(b),
+        other => Err(bad_value(
+            key,
+            format!("expected boolean, found {}", other.type_name()),
+        )),
+    }
+}
+
+fn parse_string(s: &str) -> Option<(String, &str)> {
+    let mut out = String::new();
+    let mut chars = s.strip_prefix('"')?.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Some((out, &s[i + 2..])),
+            '\\' => out.push(match chars.next()?.1 {
+                'n' => '\n',
+                't' => '\t',
+                '"' => '"',
+                '\\' => '\\',
+                _ => return None,
+            }),
+            c => out.push(c),
+        }
+    }
+    None
+}
+
+fn parse_value(s: &str) -> Result<Value, String> {
+    let (value, rest) = if s.starts_with('"') {
+        let (string, rest) = parse_string(s).ok_or("unterminated or invalid string")?;
+        (Value::String(string), rest)
+    } else {
+        let end = s
+            .find(|c: char| c.is_whitespace() || c == '#')
+            .unwrap_or(s.len());
+        let (word, rest) = s.split_at(end);
+        let value = match word {
+            "true" => Value::Boolean(true),
+            "false" => Value::Boolean(false),
+            _ => Value::Integer(
+                word.replace('_', "")
+                    .parse()
+                    .map_err(|_| format!("invalid value \"{}\"", word))?,
+            ),
+        };
+        (value, rest)
+    };
+
+    let rest = rest.trim_start();
+    if rest.is_empty() || rest.starts_with('#') {
+        Ok(value)
+    } else {
+        Err(format!("unexpected trailing characters \"{}\"", rest))
+    }
+}
+
+/// parses the subset of TOML used by `aoc.toml`: tables, comments and string, integer and
+/// boolean values. keys are returned fully qualified, e.g. `paths.inputs`.
+fn parse_toml(text: &str) -> Result<Vec<(String, Value)>, ConfigError> {
+    let mut table = String::new();
+    let mut entries = Vec::new();
+
+    for (line_num, line) in text.lines().enumerate() {
+        let syntax_error = |message: String| ConfigError::Syntax {
+            line: line_num + 1,
+            message,
+        };
+
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(header) = line.strip_prefix('[') {
+            let (name, rest) = header
+                .split_once(']')
+                .ok_or_else(|| syntax_error("unterminated table header".into()))?;
+            let rest = rest.trim_start();
+            if !(rest.is_empty() || rest.starts_with('#')) {
+                return Err(syntax_error(
+                    "unexpected characters after table header".into(),
+                ));
+            }
+            table = name.trim().to_string();
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| syntax_error("expected `key = value`".into()))?;
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(syntax_error("missing key".into()));
+        }
+        let value = parse_value(value.trim()).map_err(syntax_error)?;
+
+        let key = if table.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", table, key)
+        };
+        entries.push((key, value));
+    }
+
+    Ok(entries)
+}
+
+impl Config {
+    pub fn parse(text: &str) -> Result<Self, ConfigError> {
+        let mut config = Config::default();
+
+        for (key, value) in parse_toml(text)? {
+            match key.as_str() {
+                "year" => match value {
+                    Value::Integer(year) => {
+                        config.year = Some(
+                            u16::try_from(year)
+                                .map_err(|_| bad_value(&key, "year out of range"))?,
+                        )
+                    }
+                    other => {
+                        return Err(bad_value(
+                            &key,
+                            format!("expected integer, found {}", other.type_name()),
+                        ))
+                    }
+                },
+                "paths.inputs" => config.paths.inputs = expect_string(&key, value)?.into(),
+                "paths.examples" => config.paths.examples = expect_string(&key, value)?.into(),
+                "paths.puzzles" => config.paths.puzzles = expect_string(&key, value)?.into(),
+                "paths.answers" => config.paths.answers = expect_string(&key, value)?.into(),
+                "paths.templates" => config.paths.templates = expect_string(&key, value)?.into(),
+                "paths.history" => config.paths.history = expect_string(&key, value)?.into(),
+                "aoc.executable" => config.aoc_executable = expect_string(&key, value)?,
+                "run.release" => config.release = expect_bool(&key, value)?,
+                "run.output" => {
+                    config.output = expect_string(&key, value)?
+                        .parse()
+                        .map_err(|err: String| bad_value(&key, err))?
+                }
+                "encryption.enabled" => config.encryption.enabled = expect_bool(&key, value)?,
+                "encryption.key_env" => config.encryption.key_env = expect_string(&key, value)?,
+                "leaderboard.id" => {
+                    config.leaderboard.id = Some(
+                        u64::try_from(expect_integer(&key, value)?)
+                            .map_err(|_| bad_value(&key, "id must not be negative"))?,
+                    )
+                }
+                "leaderboard.file" => config.leaderboard.file = expect_string(&key, value)?.into(),
+                "leaderboard.client" => config.leaderboard.client = expect_string(&key, value)?,
+                _ => return Err(ConfigError::UnknownKey(key)),
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// loads the config file named by `overrides` (or `aoc.toml` in the current directory),
+    /// then applies the overrides on top. a missing default config file yields the defaults.
+    pub fn load(overrides: &Overrides) -> Result<Self, ConfigError> {
+        let path = match &overrides.config_path {
+            Some(path) => path.clone(),
+            None => env::current_dir()
+                .map_err(|e| ConfigError::Io(CONFIG_FILE.into(), e.to_string()))?
+                .join(CONFIG_FILE),
+        };
+
+        let mut config = match fs::read_to_string(&path) {
+            Ok(text) => Config::parse(&text)?,
+            Err(e)
+                if e.kind() == std::io::ErrorKind::NotFound && overrides.config_path.is_none() =>
+            {
+                Config::default()
+            }
+            Err(e) => return Err(ConfigError::Io(path, e.to_string())),
+        };
+
+        overrides.apply(&mut config);
+        Ok(config)
+    }
+}
+
+/// command-line flags that take precedence over the config file.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct Overrides {
+    pub config_path: Option<PathBuf>,
+    pub year: Option<u16>,
+    pub inputs: Option<PathBuf>,
+    pub examples: Option<PathBuf>,
+    pub puzzles: Option<PathBuf>,
+    pub answers: Option<PathBuf>,
+    pub aoc_executable: Option<String>,
+    pub release: Option<bool>,
+    pub output: Option<OutputFormat>,
+}
+
+impl Overrides {
+    /// consumes the shared override flags from `args`, leaving everything else in place.
+    pub fn from_args(args: &mut pico_args::Arguments) -> Result<Self, pico_args::Error> {
+        let release = if args.contains("--release") {
+            Some(true)
+        } else if args.contains("--debug") {
+            Some(false)
+        } else {
+            None
+        };
+
+        Ok(Overrides {
+            config_path: args.opt_value_from_str("--config")?,
+            year: args.opt_value_from_str(["-y", "--year"])?,
+            inputs: args.opt_value_from_str("--inputs-dir")?,
+            examples: args.opt_value_from_str("--examples-dir")?,
+            puzzles: args.opt_value_from_str("--puzzles-dir")?,
+            answers: args.opt_value_from_str("--answers-dir")?,
+            aoc_executable: args.opt_value_from_str("--aoc-bin")?,
+            release,
+            output: args.opt_value_from_str("--output")?,
+        })
+    }
+
+    /// renders the overrides back into flags, e.g. to forward them to a child process.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        let mut push = |flag: &str, value: Option<String>| {
+            if let Some(value) = value {
+                args.push(flag.to_string());
+                args.push(value);
+            }
+        };
+
+        let path = |p: &Option<PathBuf>| p.as_ref().map(|p| p.display().to_string());
+        push("--config", path(&self.config_path));
+        push("--year", self.year.map(|y| y.to_string()));
+        push("--inputs-dir", path(&self.inputs));
+        push("--examples-dir", path(&self.examples));
+        push("--puzzles-dir", path(&self.puzzles));
+        push("--answers-dir", path(&self.answers));
+        push("--aoc-bin", self.aoc_executable.clone());
+        push(
+            "--output",
+            self.output.map(|o| match o {
+                OutputFormat::Pretty => "pretty".into(),
+                OutputFormat::Plain => "plain".into(),
+            }),
+        );
+
+        match self.release {
+            Some(true) => args.push("--release".into()),
+            Some(false) => args.push("--debug".into()),
+            None => {}
+        }
+
+        args
+    }
+
+    fn apply(&self, config: &mut Config) {
+        if let Some(year) = self.year {
+            config.year = Some(year);
+        }
+        if let Some(inputs) = &self.inputs {
+            config.paths.inputs = inputs.clone();
+        }
+        if let Some(examples) = &self.examples {
+            config.paths.examples = examples.clone();
+        }
+        if let Some(puzzles) = &self.puzzles {
+            config.paths.puzzles = puzzles.clone();
+        }
+        if let Some(answers) = &self.answers {
+            config.paths.answers = answers.clone();
+        }
+        if let Some(executable) = &self.aoc_executable {
+            config.aoc_executable = executable.clone();
+        }
+        if let Some(release) = self.release {
+            config.release = release;
+        }
+        if let Some(output) = self.output {
+            config.output = output;
+        }
+    }
+}
+
+/// loads the config with `overrides` and installs it as the process-wide config.
+/// binaries that parse their own arguments call this before anything reads the config.
+pub fn init(overrides: &Overrides) -> Result<&'static Config, ConfigError> {
+    let config = Config::load(overrides)?;
+    Ok(CONFIG.get_or_init(|| config))
+}
+
+/// returns the process-wide config. if `init` was not called, the config is loaded on first use,
+/// honoring any override flags passed to the current process. exits on invalid flags.
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(|| {
+        let overrides = match Overrides::from_args(&mut pico_args::Arguments::from_env()) {
+            Ok(overrides) => overrides,
+            Err(e) => {
+                eprintln!("Failed to process arguments: {}", e);
+                std::process::exit(1);
+            }
+        };
+        Config::load(&overrides).unwrap_or_else(|e| panic!("invalid {}: {}", CONFIG_FILE, e))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_empty() {
+        assert_eq!(Config::parse(""), Ok(Config::default()));
+        assert_eq!(Config::parse("# only a comment\n\n"), Ok(Config::default()));
+    }
+
+    #[test]
+    fn parse_full() {
+        let config = Config::parse(
+            "year = 2022 # trailing comment\n\
+             \n\
+             [paths]\n\
+             inputs = \"data/inputs\"\n\
+             examples = \"data/examples\"\n\
+             puzzles = \"data/puzzles\"\n\
+             answers = \"data/answers\"\n\
+             templates = \"data/templates\"\n\
+             history = \"data/history.tsv\"\n\
+             \n\
+             [aoc]\n\
+             executable = \"/usr/local/bin/aoc\"\n\
+             \n\
+             [run]\n\
+             release = true\n\
+             output = \"plain\"\n\
+             \n\
+             [encryption]\n\
+             enabled = true\n\
+             key_env = \"MY_KEY\"\n\
+             \n\
+             [leaderboard]\n\
+             id = 3031\n\
+             file = \"data/leaderboard.json\"\n\
+             client = \"/opt/curl/bin/curl\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            config,
+            Config {
+                year: Some(2022),
+                paths: Paths {
+                    inputs: "data/inputs".into(),
+                    examples: "data/examples".into(),
+                    puzzles: "data/puzzles".into(),
+                    answers: "data/answers".into(),
+                    templates: "data/templates".into(),
+                    history: "data/history.tsv".into(),
+                },
+                aoc_executable: "/usr/local/bin/aoc".into(),
+                release: true,
+                output: OutputFormat::Plain,
+                encryption: Encryption {
+                    enabled: true,
+                    key_env: "MY_KEY".into(),
+                },
+                leaderboard: Leaderboard {
+                    id: Some(3031),
+                    file: "data/leaderboard.json".into(),
+                    client: "/opt/curl/bin/curl".into(),
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Config::parse("yaer = 2022"),
+            Err(ConfigError::UnknownKey("yaer".into()))
+        );
+        assert_eq!(
+            Config::parse("[paths]\ninputs = 3"),
+            Err(bad_value("paths.inputs", "expected string, found integer"))
+        );
+        assert_eq!(
+            Config::parse("\n[run]\nrelease = \"yes"),
+            Err(ConfigError::Syntax {
+                line: 3,
+                message: "unterminated or invalid string".into()
+            })
+        );
+        assert_eq!(
+            Config::parse("year 2022"),
+            Err(ConfigError::Syntax {
+                line: 1,
+                message: "expected `key = value`".into()
+            })
+        );
+        assert!(matches!(
+            Config::parse("[run]\noutput = \"fancy\""),
+            Err(ConfigError::BadValue { .. })
+        ));
+    }
+
+    #[test]
+    fn overrides_take_precedence() {
+        let mut config = Config::parse("year = 2021\n[run]\nrelease = false").unwrap();
+        let overrides = Overrides {
+            year: Some(2022),
+            release: Some(true),
+            inputs: Some("elsewhere".into()),
+            ..Overrides::default()
+        };
+        overrides.apply(&mut config);
+
+        assert_eq!(config.year, Some(2022));
+        assert!(config.release);
+        assert_eq!(config.paths.input(7), PathBuf::from("elsewhere/07.txt"));
+        assert_eq!(
+            config.paths.example(7),
+            PathBuf::from("src/examples/07.txt")
+        );
+    }
+
+    #[test]
+    fn overrides_round_trip() {
+        let overrides = Overrides {
+            year: Some(2020),
+            puzzles: Some("p".into()),
+            release: Some(false),
+            output: Some(OutputFormat::Plain),
+            ..Overrides::default()
+        };
+        let args = overrides
+            .to_args()
+            .into_iter()
+            .map(Into::into)
+            .collect::<Vec<_>>();
+        let parsed = Overrides::from_args(&mut pico_args::Arguments::from_vec(args)).unwrap();
+        assert_eq!(parsed, overrides);
+    }
+}
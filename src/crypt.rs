@@ -0,0 +1,233 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::{
+    env,
+    ffi::OsString,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use argon2::Argon2;
+use chacha20poly1305::{
+    aead::{rand_core::RngCore, Aead, AeadCore, KeyInit, OsRng},
+    Key as CipherKey, XChaCha20Poly1305, XNonce,
+};
+
+use crate::config::Encryption;
+
+pub const EXTENSION: &str = "enc";
+
+const MAGIC: &[u8] = b"AOCENC1\n";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum CryptError {
+    MissingKey(String),
+    BadFormat,
+    /// the authentication tag did not match: wrong key or tampered file.
+    Decrypt,
+    Io(PathBuf, String),
+}
+
+impl Display for CryptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CryptError::MissingKey(var) => {
+                write!(f, "environment variable {} is not set.", var)
+            }
+            CryptError::BadFormat => write!(f, "file is not an encrypted input."),
+            CryptError::Decrypt => write!(f, "could not decrypt file, is the key correct?"),
+            CryptError::Io(path, e) => write!(f, "could not access {}: {}", path.display(), e),
+        }
+    }
+}
+
+pub struct Key(String);
+
+impl Key {
+    /// the key of a passphrase. every file gets its own cipher key, derived from the passphrase
+    /// and a random salt stored in the file header.
+    pub fn from_passphrase(passphrase: &str) -> Self {
+        Key(passphrase.into())
+    }
+
+    pub fn from_env(var: &str) -> Result<Self, CryptError> {
+        match env::var(var) {
+            Ok(passphrase) if !passphrase.is_empty() => Ok(Key::from_passphrase(&passphrase)),
+            _ => Err(CryptError::MissingKey(var.into())),
+        }
+    }
+
+    fn cipher(&self, salt: &[u8]) -> XChaCha20Poly1305 {
+        // argon2id with the default parameters, which follow the OWASP recommendation
+        let mut key = CipherKey::default();
+        Argon2::default()
+            .hash_password_into(self.0.as_bytes(), salt, &mut key)
+            .expect("the salt and key lengths are valid");
+        XChaCha20Poly1305::new(&key)
+    }
+
+    pub fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
+        let mut salt = [0; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher(&salt)
+            .encrypt(&nonce, plaintext)
+            .expect("encryption cannot fail for in-memory buffers");
+        [MAGIC, &salt, nonce.as_slice(), &ciphertext].concat()
+    }
+
+    pub fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, CryptError> {
+        let data = data.strip_prefix(MAGIC).ok_or(CryptError::BadFormat)?;
+        if data.len() < SALT_LEN + NONCE_LEN {
+            return Err(CryptError::BadFormat);
+        }
+        let (salt, data) = data.split_at(SALT_LEN);
+        let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+        self.cipher(salt)
+            .decrypt(XNonce::from_slice(nonce), ciphertext)
+            .map_err(|_| CryptError::Decrypt)
+    }
+}
+
+/// path of the encrypted counterpart of `path`, e.g. `src/inputs/01.txt.enc`.
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().map(OsString::from).unwrap_or_default();
+    name.push(".");
+    name.push(EXTENSION);
+    path.with_file_name(name)
+}
+
+/// reads `path`, falling back to decrypting its `.enc` counterpart when the plain file is missing
+/// or empty and encryption is enabled.
+pub fn read(path: &Path, encryption: &Encryption) -> Result<Vec<u8>, CryptError> {
+    let io_error = |e: io::Error| CryptError::Io(path.to_path_buf(), e.to_string());
+    let plain = match fs::read(path) {
+        Ok(contents) if !contents.is_empty() || !encryption.enabled => return Ok(contents),
+        Err(e) if e.kind() != io::ErrorKind::NotFound || !encryption.enabled => {
+            return Err(io_error(e))
+        }
+        plain => plain,
+    };
+
+    match fs::read(encrypted_path(path)) {
+        Ok(data) => Key::from_env(&encryption.key_env)?.decrypt(&data),
+        Err(_) => plain.map_err(io_error),
+    }
+}
+
+/// plain `NN.txt` files in `dir`.
+pub fn plain_files(dir: &Path) -> Result<Vec<PathBuf>, CryptError> {
+    files_with_extension(dir, "txt")
+}
+
+/// encrypted `NN.txt.enc` files in `dir`.
+pub fn encrypted_files(dir: &Path) -> Result<Vec<PathBuf>, CryptError> {
+    files_with_extension(dir, EXTENSION)
+}
+
+fn files_with_extension(dir: &Path, extension: &str) -> Result<Vec<PathBuf>, CryptError> {
+    let io_error = |e: io::Error| CryptError::Io(dir.to_path_buf(), e.to_string());
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(io_error(e)),
+    };
+
+    let mut files = Vec::new();
+    for entry in entries {
+        let path = entry.map_err(io_error)?.path();
+        if path.extension().is_some_and(|e| e == extension) {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let key = Key::from_passphrase("hunter2");
+        let encrypted = key.encrypt(b"1000\n2000\n");
+
+        assert!(encrypted.starts_with(MAGIC));
+        // a fresh salt and nonce for every file
+        let again = key.encrypt(b"1000\n2000\n");
+        assert_ne!(
+            again[..MAGIC.len() + SALT_LEN],
+            encrypted[..MAGIC.len() + SALT_LEN]
+        );
+        assert_eq!(key.decrypt(&encrypted), Ok(b"1000\n2000\n".to_vec()));
+    }
+
+    #[test]
+    fn wrong_key_or_format() {
+        let encrypted = Key::from_passphrase("hunter2").encrypt(b"secret");
+
+        assert_eq!(
+            Key::from_passphrase("hunter3").decrypt(&encrypted),
+            Err(CryptError::Decrypt)
+        );
+        assert_eq!(
+            Key::from_passphrase("hunter2").decrypt(b"1000\n2000\n"),
+            Err(CryptError::BadFormat)
+        );
+        assert_eq!(
+            Key::from_passphrase("hunter2").decrypt(MAGIC),
+            Err(CryptError::BadFormat)
+        );
+    }
+
+    #[test]
+    fn paths() {
+        assert_eq!(
+            encrypted_path(Path::new("src/inputs/01.txt")),
+            PathBuf::from("src/inputs/01.txt.enc")
+        );
+    }
+
+    #[test]
+    fn transparent_read() {
+        let dir = env::temp_dir().join(format!("aoc-crypt-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let var = "AOC_CRYPT_TEST_KEY";
+        env::set_var(var, "hunter2");
+
+        let path = dir.join("01.txt");
+        fs::write(
+            encrypted_path(&path),
+            Key::from_passphrase("hunter2").encrypt(b"decrypted"),
+        )
+        .unwrap();
+
+        let enabled = Encryption {
+            enabled: true,
+            key_env: var.into(),
+        };
+        let disabled = Encryption {
+            enabled: false,
+            key_env: var.into(),
+        };
+        let decrypted = read(&path, &enabled);
+        let not_found = read(&path, &disabled);
+        fs::write(&path, "").unwrap();
+        let scaffolded = read(&path, &enabled);
+        fs::write(&path, "plain").unwrap();
+        let plain = read(&path, &enabled);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(decrypted, Ok(b"decrypted".to_vec()));
+        assert!(matches!(not_found, Err(CryptError::Io(_, _))));
+        assert_eq!(scaffolded, Ok(b"decrypted".to_vec()));
+        assert_eq!(plain, Ok(b"plain".to_vec()));
+    }
+}
@@ -0,0 +1,244 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::{
+    env,
+    fmt::Display,
+    ops::RangeInclusive,
+    panic::{self, AssertUnwindSafe},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::race::Implementation;
+
+pub const DEFAULT_RUNS: u64 = 1000;
+
+/// small, seedable pseudo random number generator (splitmix64) for input generators, so that a
+/// failing run can be reproduced from its seed.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn from_seed(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// a number in `range`.
+    pub fn range(&mut self, range: RangeInclusive<u64>) -> u64 {
+        let (start, end) = range.into_inner();
+        let span = (end - start).wrapping_add(1);
+        start
+            + if span == 0 {
+                self.next_u64()
+            } else {
+                self.next_u64() % span
+            }
+    }
+
+    pub fn usize(&mut self, range: RangeInclusive<usize>) -> usize {
+        self.range(*range.start() as u64..=*range.end() as u64) as usize
+    }
+
+    /// true with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64 <= p
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.usize(0..=items.len() - 1)]
+    }
+}
+
+/// what an implementation did with an input.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Answer {
+    Solved(Option<String>),
+    Panicked(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Solved(Some(answer)) => write!(f, "{}", answer),
+            Answer::Solved(None) => write!(f, "not solved"),
+            Answer::Panicked(message) => write!(f, "panicked: {}", message),
+        }
+    }
+}
+
+/// runs `func`, turning a panic into an answer. the panic message is not printed.
+pub fn answer<T: Display>(func: fn(&str) -> Option<T>, input: &str) -> Answer {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = panic::catch_unwind(AssertUnwindSafe(|| func(input)));
+    panic::set_hook(hook);
+
+    match result {
+        Ok(answer) => Answer::Solved(answer.map(|a| a.to_string())),
+        Err(payload) => Answer::Panicked(
+            payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".into()),
+        ),
+    }
+}
+
+/// a generated input on which the implementations of a part disagree.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Difference {
+    pub run: u64,
+    pub input: String,
+    pub answers: Vec<(&'static str, Answer)>,
+}
+
+/// generates `runs` inputs and returns the first one on which an implementation answers
+/// differently from the first implementation.
+pub fn find_difference<T: Display>(
+    generate: fn(&mut Rng) -> String,
+    implementations: &[Implementation<T>],
+    rng: &mut Rng,
+    runs: u64,
+) -> Option<Difference> {
+    (0..runs).find_map(|run| {
+        let input = generate(rng);
+        let answers = implementations
+            .iter()
+            .map(|(name, func)| (*name, answer(*func, &input)))
+            .collect::<Vec<_>>();
+        let differs = answers.iter().any(|(_, a)| a != &answers[0].1);
+        differs.then_some(Difference {
+            run,
+            input,
+            answers,
+        })
+    })
+}
+
+/// settings of a fuzzing session, read from the arguments passed to the day.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Settings {
+    pub runs: u64,
+    pub seed: u64,
+}
+
+fn flag_value(args: &[String], name: &str) -> Option<u64> {
+    let index = args.iter().position(|arg| arg == name)?;
+    args.get(index + 1)?.parse().ok()
+}
+
+/// `Some` if the day was started with `--fuzz-diff`, e.g. by `cargo fuzz-diff 08`.
+pub fn settings() -> Option<Settings> {
+    let args = env::args().collect::<Vec<_>>();
+    if !args.iter().any(|arg| arg == "--fuzz-diff") {
+        return None;
+    }
+    Some(Settings {
+        runs: flag_value(&args, "--runs").unwrap_or(DEFAULT_RUNS),
+        seed: flag_value(&args, "--seed").unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_nanos() as u64)
+        }),
+    })
+}
+
+/// fuzzes the implementations of one part and prints the outcome. returns whether they agreed.
+pub fn run_part<T: Display>(
+    part: u8,
+    generate: fn(&mut Rng) -> String,
+    implementations: &[Implementation<T>],
+    settings: &Settings,
+) -> bool {
+    let mut rng = Rng::from_seed(settings.seed);
+    match find_difference(generate, implementations, &mut rng, settings.runs) {
+        None => {
+            println!(
+                "✔ Part {}: {} implementations agree on {} generated inputs.",
+                part,
+                implementations.len(),
+                settings.runs
+            );
+            true
+        }
+        Some(difference) => {
+            println!(
+                "✘ Part {}: implementations disagree on generated input #{} (seed {}):",
+                part, difference.run, settings.seed
+            );
+            println!("---");
+            print!("{}", difference.input);
+            if !difference.input.ends_with('\n') {
+                println!();
+            }
+            println!("---");
+            for (name, answer) in &difference.answers {
+                println!("{}: {}", name, answer);
+            }
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generate(rng: &mut Rng) -> String {
+        (0..rng.usize(0..=5))
+            .map(|_| *rng.pick(&['a', 'b']))
+            .collect()
+    }
+
+    fn count_a(input: &str) -> Option<usize> {
+        Some(input.matches('a').count())
+    }
+
+    fn count_a_wrong(input: &str) -> Option<usize> {
+        Some(input.matches('a').count().min(2))
+    }
+
+    fn panics(input: &str) -> Option<usize> {
+        assert!(!input.contains("bb"), "two b's");
+        count_a(input)
+    }
+
+    #[test]
+    fn rng_is_deterministic() {
+        let mut a = Rng::from_seed(42);
+        let mut b = Rng::from_seed(42);
+        assert_eq!(
+            (0..5).map(|_| a.next_u64()).collect::<Vec<_>>(),
+            (0..5).map(|_| b.next_u64()).collect::<Vec<_>>()
+        );
+        assert!((0..100).all(|_| (3..=5).contains(&a.range(3..=5))));
+    }
+
+    #[test]
+    fn finds_differences() {
+        let agree: [Implementation<usize>; 2] = [("a", count_a), ("b", count_a)];
+        assert_eq!(
+            find_difference(generate, &agree, &mut Rng::from_seed(1), 200),
+            None
+        );
+
+        let disagree: [Implementation<usize>; 2] = [("right", count_a), ("wrong", count_a_wrong)];
+        let difference = find_difference(generate, &disagree, &mut Rng::from_seed(1), 200).unwrap();
+        assert!(difference.input.matches('a').count() > 2);
+
+        let panicking: [Implementation<usize>; 2] = [("right", count_a), ("panics", panics)];
+        let difference =
+            find_difference(generate, &panicking, &mut Rng::from_seed(1), 200).unwrap();
+        assert_eq!(difference.answers[1].1, Answer::Panicked("two b's".into()));
+    }
+}
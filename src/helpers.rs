@@ -2,3 +2,14 @@
  * Use this file if you want to extract helpers from your solutions.
  * Example import from this file: `use advent_of_code::helpers::example_fn;`.
  */
+
+pub mod combinator;
+pub mod cycle;
+pub mod geom;
+pub mod grid;
+pub mod interval;
+pub mod ocr;
+pub mod parse;
+pub mod scan;
+pub mod search;
+pub mod select;
@@ -0,0 +1,492 @@
+//! small parser combinators. a parser is any `Fn(&mut Cursor) -> Result<T, Failure>`, so
+//! grammars are plain functions that can be shared between days. a parser of your own reads
+//! `Cursor::rest` and `advance`s past what it matched:
+//!
+//! ```
+//! use advent_of_code::helpers::combinator::{integer, literal, parse, seq, Parser};
+//!
+//! let range = || seq((integer::<u32>(), literal("-"), integer::<u32>())).map(|(a, _, b)| (a, b));
+//! let pair = seq((range(), literal(","), range())).map(|(a, _, b)| (a, b));
+//! assert_eq!(parse(pair, "2-4,6-8"), Ok(((2, 4), (6, 8))));
+//! ```
+use std::{cmp::Ordering, fmt::Display, str::FromStr};
+
+use super::parse::ParseError;
+
+/// the text being parsed and how far the parser got.
+#[derive(Clone, Debug)]
+pub struct Cursor<'a> {
+    text: &'a str,
+    offset: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Cursor { text, offset: 0 }
+    }
+
+    /// the text that has not been parsed yet.
+    pub fn rest(&self) -> &'a str {
+        &self.text[self.offset..]
+    }
+
+    /// the byte offset into the text.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn is_at_end(&self) -> bool {
+        self.offset == self.text.len()
+    }
+
+    /// moves past the first `bytes` bytes of `rest`, once a parser matched them.
+    pub fn advance(&mut self, bytes: usize) {
+        self.offset += bytes;
+    }
+
+    /// moves back to `offset`, usually one saved with `offset` before trying a parser that may
+    /// not match.
+    pub fn reset(&mut self, offset: usize) {
+        self.offset = offset;
+    }
+}
+
+/// why a parser did not match.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Failure {
+    pub offset: usize,
+    /// bytes to underline, 0 for a single position.
+    pub len: usize,
+    /// what would have matched at `offset`.
+    pub expected: Vec<String>,
+    /// a message replacing the list of expectations, e.g. for a number that does not fit.
+    pub message: Option<String>,
+}
+
+impl Failure {
+    pub fn expected(offset: usize, what: impl Into<String>) -> Self {
+        Failure {
+            offset,
+            len: 0,
+            expected: vec![what.into()],
+            message: None,
+        }
+    }
+
+    pub fn message(offset: usize, len: usize, message: impl Display) -> Self {
+        Failure {
+            offset,
+            len,
+            expected: Vec::new(),
+            message: Some(message.to_string()),
+        }
+    }
+
+    /// the failure that got further into the text. failures at the same offset combine what
+    /// they expected.
+    fn merge(self, other: Failure) -> Failure {
+        match self.offset.cmp(&other.offset) {
+            Ordering::Greater => self,
+            Ordering::Less => other,
+            Ordering::Equal if self.message.is_some() => self,
+            Ordering::Equal => {
+                let mut merged = self;
+                for expected in other.expected {
+                    if !merged.expected.contains(&expected) {
+                        merged.expected.push(expected);
+                    }
+                }
+                merged
+            }
+        }
+    }
+
+    pub fn into_parse_error(self, text: &str) -> ParseError {
+        ParseError::spanning(text, self.offset..self.offset + self.len, &self)
+    }
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(message) = &self.message {
+            return write!(f, "{}", message);
+        }
+        match &self.expected[..] {
+            [] => write!(f, "unexpected input"),
+            [one] => write!(f, "expected {}", one),
+            [init @ .., last] => write!(f, "expected {} or {}", init.join(", "), last),
+        }
+    }
+}
+
+/// a parser producing `T`. implemented for every matching closure and function.
+pub trait Parser<T>: Fn(&mut Cursor<'_>) -> Result<T, Failure> {
+    fn map<U>(self, f: impl Fn(T) -> U) -> impl Parser<U>
+    where
+        Self: Sized,
+    {
+        move |c: &mut Cursor<'_>| self(c).map(&f)
+    }
+
+    /// runs `next` after this parser and keeps only this parser's result.
+    fn skip<U>(self, next: impl Parser<U>) -> impl Parser<T>
+    where
+        Self: Sized,
+    {
+        move |c: &mut Cursor<'_>| {
+            let value = self(c)?;
+            next(c)?;
+            Ok(value)
+        }
+    }
+
+    /// runs `next` after this parser and keeps only the result of `next`.
+    fn then<U>(self, next: impl Parser<U>) -> impl Parser<U>
+    where
+        Self: Sized,
+    {
+        move |c: &mut Cursor<'_>| {
+            self(c)?;
+            next(c)
+        }
+    }
+
+    /// `None` instead of a failure, leaving the cursor where it was.
+    fn optional(self) -> impl Parser<Option<T>>
+    where
+        Self: Sized,
+    {
+        move |c: &mut Cursor<'_>| {
+            let start = c.offset();
+            match self(c) {
+                Ok(value) => Ok(Some(value)),
+                Err(_) => {
+                    c.reset(start);
+                    Ok(None)
+                }
+            }
+        }
+    }
+}
+
+impl<T, F: Fn(&mut Cursor<'_>) -> Result<T, Failure>> Parser<T> for F {}
+
+/// exactly `expected`.
+pub fn literal(expected: &'static str) -> impl Parser<()> {
+    move |c: &mut Cursor<'_>| {
+        if c.rest().starts_with(expected) {
+            c.advance(expected.len());
+            Ok(())
+        } else {
+            Err(Failure::expected(c.offset(), format!("{:?}", expected)))
+        }
+    }
+}
+
+/// one or more characters matching `predicate`, described as `what` in errors.
+pub fn take_while1(predicate: impl Fn(char) -> bool, what: &'static str) -> impl Parser<String> {
+    move |c: &mut Cursor<'_>| {
+        let rest = c.rest();
+        let len = rest.find(|ch| !predicate(ch)).unwrap_or(rest.len());
+        if len == 0 {
+            return Err(Failure::expected(c.offset(), what));
+        }
+        c.advance(len);
+        Ok(rest[..len].to_string())
+    }
+}
+
+/// a run of characters other than whitespace.
+pub fn word() -> impl Parser<String> {
+    take_while1(|c| !c.is_whitespace(), "a word")
+}
+
+/// an optionally negative decimal integer of type `T`.
+pub fn integer<T>() -> impl Parser<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    move |c: &mut Cursor<'_>| {
+        let rest = c.rest();
+        let sign = usize::from(rest.starts_with('-'));
+        let digits = rest[sign..]
+            .find(|ch: char| !ch.is_ascii_digit())
+            .unwrap_or(rest.len() - sign);
+        if digits == 0 {
+            return Err(Failure::expected(c.offset(), "a number"));
+        }
+        let text = &rest[..sign + digits];
+        let value = text
+            .parse()
+            .map_err(|e| Failure::message(c.offset(), text.len(), e))?;
+        c.advance(text.len());
+        Ok(value)
+    }
+}
+
+/// zero or more spaces or tabs.
+pub fn spaces() -> impl Parser<()> {
+    move |c: &mut Cursor<'_>| {
+        let rest = c.rest();
+        c.advance(rest.len() - rest.trim_start_matches([' ', '\t']).len());
+        Ok(())
+    }
+}
+
+/// one or more spaces or tabs.
+pub fn whitespace() -> impl Parser<()> {
+    take_while1(|c| c == ' ' || c == '\t', "whitespace").map(|_| ())
+}
+
+/// the end of a line: a line break or the end of the text.
+pub fn newline() -> impl Parser<()> {
+    move |c: &mut Cursor<'_>| {
+        let rest = c.rest();
+        if rest.is_empty() {
+            Ok(())
+        } else if rest.starts_with("\r\n") {
+            c.advance(2);
+            Ok(())
+        } else if rest.starts_with('\n') {
+            c.advance(1);
+            Ok(())
+        } else {
+            Err(Failure::expected(c.offset(), "the end of the line"))
+        }
+    }
+}
+
+pub fn end() -> impl Parser<()> {
+    move |c: &mut Cursor<'_>| {
+        if c.is_at_end() {
+            Ok(())
+        } else {
+            Err(Failure::expected(c.offset(), "the end of the input"))
+        }
+    }
+}
+
+/// one or more `item`s with a `separator` between each pair.
+pub fn separated<T, S>(item: impl Parser<T>, separator: impl Parser<S>) -> impl Parser<Vec<T>> {
+    move |c: &mut Cursor<'_>| {
+        let mut items = vec![item(c)?];
+        loop {
+            let start = c.offset();
+            if separator(c).is_err() {
+                c.reset(start);
+                return Ok(items);
+            }
+            items.push(item(c)?);
+        }
+    }
+}
+
+/// `parser` followed by the end of the line.
+pub fn line<T>(parser: impl Parser<T>) -> impl Parser<T> {
+    parser.skip(newline())
+}
+
+/// one or more lines parsed by `parser`, up to a blank line or the end of the text.
+pub fn lines<T>(parser: impl Parser<T>) -> impl Parser<Vec<T>> {
+    let line = line(parser);
+    move |c: &mut Cursor<'_>| {
+        let mut items = vec![line(c)?];
+        while !c.is_at_end() && !c.rest().starts_with(['\n', '\r']) {
+            items.push(line(c)?);
+        }
+        Ok(items)
+    }
+}
+
+/// one or more blocks parsed by `parser`, separated by blank lines. `parser` is responsible for
+/// the line break at the end of its block, e.g. by using `lines`.
+pub fn blocks<T>(parser: impl Parser<T>) -> impl Parser<Vec<T>> {
+    let blank_lines = move |c: &mut Cursor<'_>| {
+        if c.is_at_end() {
+            return Err(Failure::expected(c.offset(), "a blank line"));
+        }
+        newline()(c)?;
+        while c.rest().starts_with(['\n', '\r']) {
+            newline()(c)?;
+        }
+        Ok(())
+    };
+    separated(parser, blank_lines)
+}
+
+/// several parsers of which the first one to match is used. see `alt`.
+pub trait Alternatives<T> {
+    fn parse_first(&self, c: &mut Cursor<'_>) -> Result<T, Failure>;
+}
+
+/// several parsers run one after another. see `seq`.
+pub trait Sequence<T> {
+    fn parse_all(&self, c: &mut Cursor<'_>) -> Result<T, Failure>;
+}
+
+macro_rules! tuple_impls {
+    ($(($($parser:ident $value:ident $index:tt),+)),+ $(,)?) => {$(
+        impl<T, $($parser: Parser<T>),+> Alternatives<T> for ($($parser,)+) {
+            fn parse_first(&self, c: &mut Cursor<'_>) -> Result<T, Failure> {
+                let start = c.offset();
+                let mut failure: Option<Failure> = None;
+                $(
+                    match (self.$index)(c) {
+                        Ok(value) => return Ok(value),
+                        Err(e) => {
+                            failure = Some(match failure {
+                                Some(failure) => failure.merge(e),
+                                None => e,
+                            });
+                            c.reset(start);
+                        }
+                    }
+                )+
+                Err(failure.unwrap())
+            }
+        }
+
+        impl<$($value, $parser: Parser<$value>),+> Sequence<($($value,)+)> for ($($parser,)+) {
+            fn parse_all(&self, c: &mut Cursor<'_>) -> Result<($($value,)+), Failure> {
+                Ok(($((self.$index)(c)?,)+))
+            }
+        }
+    )+};
+}
+
+tuple_impls!(
+    (A TA 0, B TB 1),
+    (A TA 0, B TB 1, C TC 2),
+    (A TA 0, B TB 1, C TC 2, D TD 3),
+    (A TA 0, B TB 1, C TC 2, D TD 3, E TE 4),
+    (A TA 0, B TB 1, C TC 2, D TD 3, E TE 4, F TF 5),
+    (A TA 0, B TB 1, C TC 2, D TD 3, E TE 4, F TF 5, G TG 6),
+    (A TA 0, B TB 1, C TC 2, D TD 3, E TE 4, F TF 5, G TG 6, H TH 7),
+);
+
+/// the result of the first parser in the tuple that matches. if none does, the error lists
+/// what each of them expected.
+pub fn alt<T>(parsers: impl Alternatives<T>) -> impl Parser<T> {
+    move |c: &mut Cursor<'_>| parsers.parse_first(c)
+}
+
+/// the results of all parsers in the tuple, in order.
+pub fn seq<T>(parsers: impl Sequence<T>) -> impl Parser<T> {
+    move |c: &mut Cursor<'_>| parsers.parse_all(c)
+}
+
+/// parses all of `text`, allowing trailing line breaks.
+pub fn parse<T>(parser: impl Parser<T>, text: &str) -> Result<T, ParseError> {
+    let trimmed = text.trim_end_matches(['\n', '\r']);
+    let mut cursor = Cursor::new(trimmed);
+    parser.skip(end())(&mut cursor).map_err(|failure| failure.into_parse_error(text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    enum Command {
+        Move(u32, u32),
+        Wait,
+    }
+
+    fn command() -> impl Parser<Command> {
+        alt((
+            seq((
+                literal("move"),
+                whitespace(),
+                integer(),
+                literal(","),
+                spaces(),
+                integer(),
+            ))
+            .map(|(_, _, x, _, _, y)| Command::Move(x, y)),
+            literal("wait").map(|_| Command::Wait),
+        ))
+    }
+
+    /// a primitive written like one outside of this module would be.
+    fn hex_digit() -> impl Parser<u32> {
+        move |c: &mut Cursor<'_>| {
+            let digit = c.rest().chars().next().and_then(|ch| ch.to_digit(16));
+            let digit = digit.ok_or_else(|| Failure::expected(c.offset(), "a hex digit"))?;
+            c.advance(1);
+            Ok(digit)
+        }
+    }
+
+    #[test]
+    fn custom_primitive() {
+        assert_eq!(
+            parse(separated(hex_digit(), literal(":")), "a:3:F"),
+            Ok(vec![10, 3, 15])
+        );
+        assert_eq!(
+            parse(seq((hex_digit().optional(), literal("x"))), "x"),
+            Ok((None, ()))
+        );
+        let error = parse(hex_digit(), "g").unwrap_err();
+        assert_eq!(error.cause, "expected a hex digit");
+
+        let mut cursor = Cursor::new("ab");
+        cursor.advance(1);
+        cursor.reset(0);
+        assert_eq!(cursor.rest(), "ab");
+    }
+
+    #[test]
+    fn primitives() {
+        assert_eq!(parse(integer::<i32>(), "-42"), Ok(-42));
+        assert_eq!(parse(word(), "abc"), Ok("abc".to_string()));
+        assert_eq!(
+            parse(separated(integer::<u8>(), literal(",")), "1,2,3"),
+            Ok(vec![1, 2, 3])
+        );
+        assert_eq!(
+            parse(literal("a").then(integer::<u8>().optional()), "a"),
+            Ok(None)
+        );
+
+        let error = parse(integer::<u8>(), "300").unwrap_err();
+        assert_eq!((error.column, error.len), (1, 3));
+        assert_eq!(error.cause, "number too large to fit in target type");
+    }
+
+    #[test]
+    fn alternatives_and_sequences() {
+        assert_eq!(parse(command(), "move 1,2"), Ok(Command::Move(1, 2)));
+        assert_eq!(parse(command(), "wait\n"), Ok(Command::Wait));
+
+        let error = parse(command(), "move 1;2").unwrap_err();
+        assert_eq!((error.column, error.cause.as_str()), (7, "expected \",\""));
+
+        let error = parse(command(), "jump").unwrap_err();
+        assert_eq!(error.cause, "expected \"move\" or \"wait\"");
+
+        let error = parse(command(), "wait now").unwrap_err();
+        assert_eq!(
+            (error.column, error.cause.as_str()),
+            (5, "expected the end of the input")
+        );
+    }
+
+    #[test]
+    fn lines_and_blocks() {
+        let input = "wait\nmove 1, 2\n\n\nwait\n";
+        assert_eq!(
+            parse(blocks(lines(command())), input),
+            Ok(vec![
+                vec![Command::Wait, Command::Move(1, 2)],
+                vec![Command::Wait]
+            ])
+        );
+
+        let error = parse(blocks(lines(command())), "wait\nwait\nmove x\n").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 6));
+        assert_eq!(error.text, "move x");
+        assert_eq!(error.cause, "expected a number");
+    }
+}
@@ -0,0 +1,182 @@
+//! finds where a simulation starts repeating itself, to skip ahead to a far away step:
+//!
+//! ```
+//! use advent_of_code::helpers::cycle::fast_forward;
+//!
+//! // the last two digits of powers of 7 repeat every 4 steps
+//! let state = fast_forward(1u32, |&x| x * 7 % 100, 1_000_000_000);
+//! assert_eq!(state, 1);
+//! ```
+use std::{collections::HashMap, hash::Hash};
+
+/// the states from step `start` on repeat every `length` steps. step 0 is the initial state.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// the first step with the same state as step `n`.
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// finds the cycle with Brent's algorithm, which stores only two states and usually takes fewer
+/// steps than Floyd's. never returns if the states do not repeat.
+pub fn brent<S: Clone + Eq>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    // find the length by moving the tortoise to the hare at every power of two
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // with the hare `length` steps ahead, they meet at the start of the cycle
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+    Cycle { start, length }
+}
+
+/// finds the cycle with Floyd's tortoise and hare, which stores only two states. never returns if
+/// the states do not repeat.
+pub fn floyd<S: Clone + Eq>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    // the hare moves twice as fast, so they meet inside the cycle
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        let halfway = step(&hare);
+        hare = step(&halfway);
+    }
+
+    let mut start = 0;
+    let mut tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+    Cycle { start, length }
+}
+
+/// the states from step 0 on, until one repeats or step `last` is reached. with a cycle, the
+/// states end with the last one of the first cycle.
+fn remember<S: Clone + Eq + Hash>(
+    initial: S,
+    mut step: impl FnMut(&S) -> S,
+    last: usize,
+) -> (Option<Cycle>, Vec<S>) {
+    let mut seen = HashMap::new();
+    let mut states = vec![initial];
+    while states.len() <= last {
+        let current = states.last().unwrap();
+        let n = states.len() - 1;
+        if let Some(&start) = seen.get(current) {
+            states.pop();
+            let length = n - start;
+            return (Some(Cycle { start, length }), states);
+        }
+        seen.insert(current.clone(), n);
+        let next = step(current);
+        states.push(next);
+    }
+    (None, states)
+}
+
+/// finds the cycle by remembering every state, which takes the fewest steps. returns the states
+/// from step 0 up to the end of the first cycle. never returns if the states do not repeat.
+pub fn history<S: Clone + Eq + Hash>(initial: S, step: impl FnMut(&S) -> S) -> (Cycle, Vec<S>) {
+    match remember(initial, step, usize::MAX) {
+        (Some(cycle), states) => (cycle, states),
+        (None, _) => unreachable!("more than usize::MAX states"),
+    }
+}
+
+/// the state after `n` steps, running only until the states repeat.
+pub fn fast_forward<S: Clone + Eq + Hash>(initial: S, step: impl FnMut(&S) -> S, n: usize) -> S {
+    let (cycle, mut states) = remember(initial, step, n);
+    let index = cycle.map_or(n, |cycle| cycle.equivalent_step(n));
+    states.swap_remove(index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0, 1, 2, then 3, 4, 5, 6 over and over.
+    fn step(&x: &u32) -> u32 {
+        if x < 6 {
+            x + 1
+        } else {
+            3
+        }
+    }
+
+    #[test]
+    fn find_cycles() {
+        let cycle = Cycle {
+            start: 3,
+            length: 4,
+        };
+        assert_eq!(brent(0, step), cycle);
+        assert_eq!(floyd(0, step), cycle);
+        let (found, states) = history(0, step);
+        assert_eq!(found, cycle);
+        assert_eq!(states, vec![0, 1, 2, 3, 4, 5, 6]);
+
+        // a cycle of one state, starting right away
+        let fixed = Cycle {
+            start: 0,
+            length: 1,
+        };
+        assert_eq!(brent(5, |&x| x), fixed);
+        assert_eq!(floyd(5, |&x| x), fixed);
+        assert_eq!(history(5, |&x| x).0, fixed);
+
+        let collatz = |&x: &u64| if x % 2 == 0 { x / 2 } else { 3 * x + 1 };
+        assert_eq!(brent(27, collatz), history(27, collatz).0);
+        assert_eq!(floyd(27, collatz), history(27, collatz).0);
+    }
+
+    #[test]
+    fn skip_ahead() {
+        let cycle = brent(0, step);
+        assert_eq!(cycle.equivalent_step(2), 2);
+        assert_eq!(cycle.equivalent_step(9), 5);
+        for n in 0..20 {
+            let naive = (0..n).fold(0, |x, _| step(&x));
+            assert_eq!(fast_forward(0, step, n), naive);
+        }
+        assert_eq!(fast_forward(0, step, 1_000_000_000), 4);
+    }
+}
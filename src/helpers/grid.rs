@@ -0,0 +1,368 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use super::geom::{Direction, Point};
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum GridError {
+    /// a line is not as long as the first one. lines are numbered from 1.
+    Ragged {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// a character the cell parser rejected. lines and columns are numbered from 1.
+    Cell { line: usize, column: usize, c: char },
+    /// the number of cells is not a multiple of the width.
+    Size { width: usize, cells: usize },
+}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridError::Ragged {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {} has {} cells, expected {} like the first line",
+                line, found, expected
+            ),
+            GridError::Cell { line, column, c } => {
+                write!(f, "unexpected {:?} at line {}, column {}", c, line, column)
+            }
+            GridError::Size { width, cells } => {
+                write!(f, "{} cells do not fill rows of width {}", cells, width)
+            }
+        }
+    }
+}
+
+/// a rectangular grid stored row by row. positions are `(x, y)` with `(0, 0)` at the top left.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// a grid with every cell set to `fill`. a grid of width 0 has no rows, whatever `height` is.
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        let height = if width == 0 { 0 } else { height };
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Result<Self, GridError> {
+        if width == 0 && !cells.is_empty() || width != 0 && cells.len() % width != 0 {
+            return Err(GridError::Size {
+                width,
+                cells: cells.len(),
+            });
+        }
+        Ok(Grid {
+            width,
+            height: cells.len().checked_div(width).unwrap_or(0),
+            cells,
+        })
+    }
+
+    /// parses one cell per character. blank lines at the end are ignored.
+    pub fn parse_with<F>(text: &str, mut parse_cell: F) -> Result<Self, GridError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let lines = text.trim_end_matches(['\n', '\r']).lines();
+        let mut cells = Vec::new();
+        let mut width = None;
+        for (y, line) in lines.enumerate() {
+            let start = cells.len();
+            for (x, c) in line.chars().enumerate() {
+                cells.push(parse_cell(c).ok_or(GridError::Cell {
+                    line: y + 1,
+                    column: x + 1,
+                    c,
+                })?);
+            }
+            let found = cells.len() - start;
+            match width {
+                None => width = Some(found),
+                Some(expected) if expected != found => {
+                    return Err(GridError::Ragged {
+                        line: y + 1,
+                        expected,
+                        found,
+                    })
+                }
+                _ => {}
+            }
+        }
+        Grid::from_vec(width.unwrap_or(0), cells)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn index_of(&self, (x, y): (usize, usize)) -> Option<usize> {
+        (x < self.width && y < self.height).then(|| y * self.width + x)
+    }
+
+    pub fn contains(&self, (x, y): (usize, usize)) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, position: (usize, usize)) -> Option<&T> {
+        self.index_of(position).map(|i| &self.cells[i])
+    }
+
+    /// the cell at `point`, if the point lies inside the grid.
+    pub fn get_point(&self, point: Point) -> Option<&T> {
+        self.get(point.to_index()?)
+    }
+
+    pub fn get_mut(&mut self, position: (usize, usize)) -> Option<&mut T> {
+        self.index_of(position).map(|i| &mut self.cells[i])
+    }
+
+    /// `position` moved one step in `direction`, if that is still inside the grid.
+    pub fn step(&self, (x, y): (usize, usize), direction: Direction) -> Option<(usize, usize)> {
+        let (dx, dy) = direction.delta();
+        let next = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        self.contains(next).then_some(next)
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a width of 0, which only an empty grid can have.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            x < self.width,
+            "column {} outside of a grid of width {}",
+            x,
+            self.width
+        );
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// the cells from `start` in `direction` up to the edge, not including `start` itself.
+    pub fn ray(
+        &self,
+        start: (usize, usize),
+        direction: Direction,
+    ) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let mut position = Some(start);
+        std::iter::from_fn(move || {
+            position = self.step(position?, direction);
+            position.map(|p| (p, &self[p]))
+        })
+    }
+
+    fn neighbours<'a>(
+        &'a self,
+        position: (usize, usize),
+        directions: &'a [Direction],
+    ) -> impl Iterator<Item = ((usize, usize), &'a T)> {
+        directions
+            .iter()
+            .filter_map(move |&direction| self.step(position, direction))
+            .map(|p| (p, &self[p]))
+    }
+
+    /// the orthogonal neighbours inside the grid, clockwise starting at north.
+    pub fn neighbours_4(
+        &self,
+        position: (usize, usize),
+    ) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.neighbours(position, &Direction::CARDINAL)
+    }
+
+    /// the orthogonal and diagonal neighbours inside the grid, clockwise starting at north.
+    pub fn neighbours_8(
+        &self,
+        position: (usize, usize),
+    ) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.neighbours(position, &Direction::ALL)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl Grid<char> {
+    pub fn parse(text: &str) -> Result<Self, GridError> {
+        Grid::parse_with(text, Some)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: (usize, usize)) -> &T {
+        match self.index_of(position) {
+            Some(i) => &self.cells[i],
+            None => panic!(
+                "{:?} outside of a {}x{} grid",
+                position, self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, position: (usize, usize)) -> &mut T {
+        match self.index_of(position) {
+            Some(i) => &mut self.cells[i],
+            None => panic!(
+                "{:?} outside of a {}x{} grid",
+                position, self.width, self.height
+            ),
+        }
+    }
+}
+
+/// one line per row, without a trailing newline.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "abc\ndef\n";
+
+    #[test]
+    fn parse() {
+        let grid = Grid::parse(TEXT).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 'f');
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get_point(Point::new(1, 0)), Some(&'b'));
+        assert_eq!(grid.get_point(Point::new(-1, 0)), None);
+        assert_eq!(grid.to_string(), "abc\ndef");
+
+        assert_eq!(
+            Grid::parse("abc\nde\n"),
+            Err(GridError::Ragged {
+                line: 2,
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            Grid::parse_with("12\n3x\n", |c| c.to_digit(10)),
+            Err(GridError::Cell {
+                line: 2,
+                column: 2,
+                c: 'x'
+            })
+        );
+        let empty = Grid::parse("").unwrap();
+        assert_eq!(
+            (empty.width(), empty.height(), empty.rows().count()),
+            (0, 0, 0)
+        );
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = Grid::parse(TEXT).unwrap();
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(
+            grid.columns()
+                .map(|column| column.collect::<String>())
+                .collect::<Vec<_>>(),
+            vec!["ad", "be", "cf"]
+        );
+        assert_eq!(grid.iter().nth(4), Some(((1, 1), &'e')));
+    }
+
+    #[test]
+    fn rays_and_neighbours() {
+        let grid = Grid::parse_with("123\n456\n789\n", |c| c.to_digit(10)).unwrap();
+        let ray = |start, direction| {
+            grid.ray(start, direction)
+                .map(|(_, &cell)| cell)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ray((0, 1), Direction::East), vec![5, 6]);
+        assert_eq!(ray((2, 2), Direction::NorthWest), vec![5, 1]);
+        assert_eq!(ray((0, 0), Direction::North), vec![]);
+
+        let neighbours = |position| {
+            grid.neighbours_4(position)
+                .map(|(_, &cell)| cell)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(neighbours((1, 1)), vec![2, 6, 8, 4]);
+        assert_eq!(neighbours((0, 0)), vec![2, 4]);
+        assert_eq!(grid.neighbours_8((1, 1)).count(), 8);
+        assert_eq!(grid.neighbours_8((2, 2)).count(), 3);
+    }
+
+    #[test]
+    fn modify() {
+        let mut grid = Grid::new(2, 2, 0);
+        grid[(1, 0)] = 5;
+        *grid.get_mut((0, 1)).unwrap() += 1;
+        assert_eq!(grid.map(|&n| n * 2).to_string(), "010\n20");
+        let empty = Grid::new(0, 3, 0);
+        assert_eq!((empty.height(), empty.rows().count()), (0, 0));
+        assert_eq!(
+            Grid::from_vec(3, vec![1, 2, 3, 4]),
+            Err(GridError::Size { width: 3, cells: 4 })
+        );
+    }
+}
@@ -0,0 +1,321 @@
+use std::{
+    fmt::Display,
+    ops::{Range, RangeInclusive},
+};
+
+/// values with a next and a previous value, which lets intervals convert between inclusive and
+/// exclusive ends and count their values.
+pub trait Discrete: Copy + Ord {
+    fn next(self) -> Self;
+    fn previous(self) -> Self;
+    /// the number of values in `start..end`.
+    fn distance(start: Self, end: Self) -> u64;
+}
+
+macro_rules! discrete_impls {
+    ($($t:ty),+) => {$(
+        impl Discrete for $t {
+            fn next(self) -> Self {
+                self + 1
+            }
+
+            fn previous(self) -> Self {
+                self - 1
+            }
+
+            fn distance(start: Self, end: Self) -> u64 {
+                if start < end {
+                    end.abs_diff(start) as u64
+                } else {
+                    0
+                }
+            }
+        }
+    )+};
+}
+
+discrete_impls!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// the values from `start` up to but not including `end`. the interval is empty if `start` is
+/// not less than `end`.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Copy + Ord> Interval<T> {
+    /// the values in `start..end`.
+    pub fn new(start: T, end: T) -> Self {
+        Interval { start, end }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    /// whether every value of `other` is in this interval.
+    pub fn covers(&self, other: &Interval<T>) -> bool {
+        other.is_empty() || self.start <= other.start && other.end <= self.end
+    }
+
+    /// whether the intervals have a value in common.
+    pub fn overlaps(&self, other: &Interval<T>) -> bool {
+        !self.intersection(other).is_empty()
+    }
+
+    /// the values in both intervals, possibly none.
+    pub fn intersection(&self, other: &Interval<T>) -> Interval<T> {
+        Interval::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    /// the values in either interval, if they overlap or touch so that the result is an interval.
+    pub fn union(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        if self.is_empty() {
+            return Some(*other);
+        }
+        if other.is_empty() {
+            return Some(*self);
+        }
+        (self.start <= other.end && other.start <= self.end)
+            .then(|| Interval::new(self.start.min(other.start), self.end.max(other.end)))
+    }
+
+    /// the values of this interval that are not in `other`: the parts before and after it.
+    pub fn difference(&self, other: &Interval<T>) -> (Option<Interval<T>>, Option<Interval<T>>) {
+        if self.is_empty() {
+            return (None, None);
+        }
+        if !self.overlaps(other) {
+            return if self.start >= other.end && !other.is_empty() {
+                (None, Some(*self))
+            } else {
+                (Some(*self), None)
+            };
+        }
+        let before = Interval::new(self.start, other.start);
+        let after = Interval::new(other.end, self.end);
+        (
+            Some(before).filter(|i| !i.is_empty()),
+            Some(after).filter(|i| !i.is_empty()),
+        )
+    }
+}
+
+impl<T: Discrete> Interval<T> {
+    /// the values in `start..=end`. panics if `end` is the largest value of `T`.
+    pub fn inclusive(start: T, end: T) -> Self {
+        Interval::new(start, end.next())
+    }
+
+    /// the last value in the interval, if it is not empty.
+    pub fn last(&self) -> Option<T> {
+        (!self.is_empty()).then(|| self.end.previous())
+    }
+
+    /// the number of values in the interval.
+    pub fn len(&self) -> u64 {
+        T::distance(self.start, self.end)
+    }
+}
+
+impl<T: Copy + Ord> From<Range<T>> for Interval<T> {
+    fn from(range: Range<T>) -> Self {
+        Interval::new(range.start, range.end)
+    }
+}
+
+impl<T: Discrete> From<RangeInclusive<T>> for Interval<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        Interval::inclusive(*range.start(), *range.end())
+    }
+}
+
+impl<T: Display> Display for Interval<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}, {})", self.start, self.end)
+    }
+}
+
+/// a set of values stored as sorted, disjoint intervals. overlapping and touching intervals are
+/// merged when they are inserted, and lookups are binary searches.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet {
+            intervals: Vec::new(),
+        }
+    }
+}
+
+impl<T: Copy + Ord> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// the disjoint intervals of the set, in order.
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn insert(&mut self, interval: impl Into<Interval<T>>) {
+        let interval = interval.into();
+        if interval.is_empty() {
+            return;
+        }
+        // the intervals from `first` to `last` overlap or touch the new one
+        let first = self.intervals.partition_point(|i| i.end < interval.start);
+        let last = self.intervals.partition_point(|i| i.start <= interval.end);
+        let merged = self.intervals[first..last]
+            .iter()
+            .fold(interval, |merged, i| merged.union(i).unwrap());
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    pub fn remove(&mut self, interval: impl Into<Interval<T>>) {
+        let interval = interval.into();
+        if interval.is_empty() {
+            return;
+        }
+        // the intervals from `first` to `last` overlap the removed one
+        let first = self.intervals.partition_point(|i| i.end <= interval.start);
+        let last = self.intervals.partition_point(|i| i.start < interval.end);
+        if first == last {
+            return;
+        }
+        let (before, _) = self.intervals[first].difference(&interval);
+        let (_, after) = self.intervals[last - 1].difference(&interval);
+        self.intervals
+            .splice(first..last, before.into_iter().chain(after));
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let index = self.intervals.partition_point(|i| i.end <= value);
+        self.intervals.get(index).is_some_and(|i| i.contains(value))
+    }
+
+    /// whether every value of `interval` is in the set.
+    pub fn covers(&self, interval: &Interval<T>) -> bool {
+        if interval.is_empty() {
+            return true;
+        }
+        let index = self.intervals.partition_point(|i| i.end <= interval.start);
+        self.intervals
+            .get(index)
+            .is_some_and(|i| i.covers(interval))
+    }
+
+    /// the parts of `within` that are not in the set, in order.
+    pub fn gaps(&self, within: Interval<T>) -> Vec<Interval<T>> {
+        let mut gaps = Vec::new();
+        let mut start = within.start;
+        for i in &self.intervals {
+            if i.end <= start {
+                continue;
+            }
+            if i.start >= within.end {
+                break;
+            }
+            if start < i.start {
+                gaps.push(Interval::new(start, i.start));
+            }
+            start = i.end;
+        }
+        if start < within.end {
+            gaps.push(Interval::new(start, within.end));
+        }
+        gaps
+    }
+}
+
+impl<T: Discrete> IntervalSet<T> {
+    /// the number of values in the set.
+    pub fn len(&self) -> u64 {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+}
+
+impl<T: Copy + Ord, I: Into<Interval<T>>> FromIterator<I> for IntervalSet<T> {
+    fn from_iter<It: IntoIterator<Item = I>>(iter: It) -> Self {
+        let mut set = IntervalSet::new();
+        for interval in iter {
+            set.insert(interval);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intervals() {
+        let a = Interval::inclusive(2, 6);
+        let b = Interval::from(4..9);
+        assert_eq!((a.len(), a.last()), (5, Some(6)));
+        assert!(a.contains(6) && !b.contains(9));
+        assert!(a.overlaps(&b) && !a.covers(&b));
+        assert!(a.covers(&Interval::inclusive(3, 4)));
+        assert_eq!(a.intersection(&b), Interval::new(4, 7));
+        assert_eq!(a.union(&b), Some(Interval::new(2, 9)));
+        assert_eq!(a.union(&Interval::new(7, 8)), Some(Interval::new(2, 8)));
+        assert_eq!(a.union(&Interval::new(8, 9)), None);
+        assert_eq!(
+            a.difference(&Interval::new(3, 5)),
+            (Some(Interval::new(2, 3)), Some(Interval::new(5, 7)))
+        );
+        assert_eq!(a.difference(&b), (Some(Interval::new(2, 4)), None));
+        assert_eq!(a.difference(&Interval::new(0, 10)), (None, None));
+        assert_eq!(a.difference(&Interval::new(0, 2)), (None, Some(a)));
+        assert_eq!(a.difference(&Interval::new(7, 9)), (Some(a), None));
+        assert_eq!(a.difference(&Interval::new(9, 9)), (Some(a), None));
+        assert!(Interval::new(3, 3).is_empty());
+        assert_eq!(Interval::new(-3i64, -5).len(), 0);
+        assert_eq!(a.to_string(), "[2, 7)");
+    }
+
+    #[test]
+    fn interval_sets() {
+        let mut set: IntervalSet<i32> = [0..=3, 10..=12, 5..=6].into_iter().collect();
+        set.insert(4..5);
+        assert_eq!(
+            set.intervals(),
+            &[Interval::new(0, 7), Interval::new(10, 13)]
+        );
+        assert_eq!(set.len(), 10);
+        assert!(set.contains(6) && !set.contains(7) && set.contains(10));
+        assert!(set.covers(&Interval::inclusive(1, 6)));
+        assert!(!set.covers(&Interval::inclusive(6, 10)));
+        assert_eq!(
+            set.gaps(Interval::new(-1, 15)),
+            vec![
+                Interval::new(-1, 0),
+                Interval::new(7, 10),
+                Interval::new(13, 15)
+            ]
+        );
+
+        set.remove(2..11);
+        assert_eq!(
+            set.intervals(),
+            &[Interval::new(0, 2), Interval::new(11, 13)]
+        );
+        set.insert(-5..20);
+        assert_eq!(set.intervals(), &[Interval::new(-5, 20)]);
+        set.remove(-5..20);
+        assert!(set.is_empty());
+    }
+}
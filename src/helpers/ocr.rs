@@ -0,0 +1,221 @@
+//! reads the block letters some puzzles draw as their answer:
+//!
+//! ```
+//! use advent_of_code::helpers::ocr::recognise_text;
+//!
+//! let text = "\
+//! .#..#.###.
+//! .#..#..#..
+//! .####..#..
+//! .#..#..#..
+//! .#..#..#..
+//! .#..#.###.";
+//! assert_eq!(recognise_text(text), Ok("HI".to_string()));
+//! ```
+use std::fmt::Display;
+
+use super::grid::{Grid, GridError};
+
+/// the letters of a font, drawn side by side the way the puzzles draw them.
+struct Font {
+    letters: &'static str,
+    drawing: &'static str,
+}
+
+/// the small font, 4 cells wide (`Y` is 5) and 6 high.
+const SMALL: Font = Font {
+    letters: "ABCEFGHIJKLOPRSUYZ",
+    drawing: "\
+.##..###...##..####.####..##..#..#..###...##.#..#.#.....##..###..###...###.#..#.#...#.####
+#..#.#..#.#..#.#....#....#..#.#..#...#.....#.#.#..#....#..#.#..#.#..#.#....#..#.#...#....#
+#..#.###..#....###..###..#....####...#.....#.##...#....#..#.#..#.#..#.#....#..#..#.#....#.
+####.#..#.#....#....#....#.##.#..#...#.....#.#.#..#....#..#.###..###...##..#..#...#....#..
+#..#.#..#.#..#.#....#....#..#.#..#...#..#..#.#.#..#....#..#.#....#.#.....#.#..#...#...#...
+#..#.###...##..####.#.....###.#..#..###..##..#..#.####..##..#....#..#.###...##....#...####",
+};
+
+/// the large font, 6 cells wide and 10 high.
+const LARGE: Font = Font {
+    letters: "ABCEFGHJKLNPRXZ",
+    drawing: "\
+..##...#####...####..######.######..####..#....#....###.#....#.#......#....#.#####..#####..#....#.######
+.#..#..#....#.#....#.#......#......#....#.#....#.....#..#...#..#......##...#.#....#.#....#.#....#......#
+#....#.#....#.#......#......#......#......#....#.....#..#..#...#......##...#.#....#.#....#..#..#.......#
+#....#.#....#.#......#......#......#......#....#.....#..#.#....#......#.#..#.#....#.#....#..#..#......#.
+#....#.#####..#......#####..#####..#......######.....#..##.....#......#.#..#.#####..#####....##......#..
+######.#....#.#......#......#......#..###.#....#.....#..##.....#......#..#.#.#......#..#.....##.....#...
+#....#.#....#.#......#......#......#....#.#....#.....#..#.#....#......#..#.#.#......#...#...#..#...#....
+#....#.#....#.#......#......#......#....#.#....#.#...#..#..#...#......#...##.#......#...#...#..#..#.....
+#....#.#....#.#....#.#......#......#...##.#....#.#...#..#...#..#......#...##.#......#....#.#....#.#.....
+#....#.#####...####..######.#.......###.#.#....#..###...#....#.######.#....#.#......#....#.#....#.######",
+};
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum OcrError {
+    /// the text is not a grid of `#` and `.`.
+    Grid(GridError),
+    /// the letters are neither 6 nor 10 cells high.
+    Height(usize),
+    /// glyphs that are not in the font, by the column they start at. `text` has a `?` in their
+    /// place.
+    Unknown { text: String, columns: Vec<usize> },
+}
+
+impl Display for OcrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OcrError::Grid(err) => write!(f, "{}", err),
+            OcrError::Height(height) => {
+                write!(f, "letters are {} cells high, expected 6 or 10", height)
+            }
+            OcrError::Unknown { text, columns } => {
+                let columns = columns.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+                write!(
+                    f,
+                    "unknown letters at columns {} in {:?}",
+                    columns.join(", "),
+                    text
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for OcrError {}
+
+/// reads the letters drawn by the `true` cells of `grid`. empty rows above and below the letters
+/// are ignored, and letters are told apart by the empty columns between them.
+pub fn recognise(grid: &Grid<bool>) -> Result<String, OcrError> {
+    let (height, letters) = glyphs(grid);
+    if letters.is_empty() {
+        return Ok(String::new());
+    }
+    let font = match height {
+        6 => SMALL,
+        10 => LARGE,
+        _ => return Err(OcrError::Height(height)),
+    };
+    let (_, font_glyphs) = glyphs(&drawing(font.drawing).unwrap());
+
+    let mut text = String::new();
+    let mut unknown = Vec::new();
+    for (column, glyph) in letters {
+        let letter = font_glyphs
+            .iter()
+            .zip(font.letters.chars())
+            .find(|((_, font_glyph), _)| *font_glyph == glyph);
+        match letter {
+            Some((_, c)) => text.push(c),
+            None => {
+                text.push('?');
+                unknown.push(column);
+            }
+        }
+    }
+    if unknown.is_empty() {
+        Ok(text)
+    } else {
+        Err(OcrError::Unknown {
+            text,
+            columns: unknown,
+        })
+    }
+}
+
+/// reads letters drawn with `#` on a background of `.` or spaces.
+pub fn recognise_text(text: &str) -> Result<String, OcrError> {
+    recognise(&drawing(text)?)
+}
+
+fn drawing(text: &str) -> Result<Grid<bool>, OcrError> {
+    Grid::parse_with(text.trim_start_matches(['\n', '\r']), |c| match c {
+        '#' => Some(true),
+        '.' | ' ' => Some(false),
+        _ => None,
+    })
+    .map_err(OcrError::Grid)
+}
+
+/// the height of the filled rows and every run of filled columns in them, as the column it
+/// starts at and its cells.
+fn glyphs(grid: &Grid<bool>) -> (usize, Vec<(usize, Vec<bool>)>) {
+    let rows = (0..grid.height())
+        .filter(|&y| grid.row(y).contains(&true))
+        .collect::<Vec<_>>();
+    let (top, bottom) = match (rows.first(), rows.last()) {
+        (Some(&top), Some(&bottom)) => (top, bottom),
+        _ => return (0, Vec::new()),
+    };
+    let filled = |x: usize| (top..=bottom).any(|y| grid[(x, y)]);
+
+    let mut glyphs = Vec::new();
+    let mut x = 0;
+    while x < grid.width() {
+        if !filled(x) {
+            x += 1;
+            continue;
+        }
+        let start = x;
+        while x < grid.width() && filled(x) {
+            x += 1;
+        }
+        let cells = (top..=bottom)
+            .flat_map(|y| (start..x).map(move |x| grid[(x, y)]))
+            .collect();
+        glyphs.push((start, cells));
+    }
+    (bottom - top + 1, glyphs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fonts() {
+        for font in [SMALL, LARGE] {
+            assert_eq!(recognise_text(font.drawing), Ok(font.letters.to_string()));
+        }
+        let grid = drawing(LARGE.drawing).unwrap().map(|&filled| !filled);
+        assert!(recognise(&grid).is_err());
+    }
+
+    #[test]
+    fn recognise_letters() {
+        let text = "
+ ##  #### #  #
+#  # #    #  #
+#    ###  ####
+#    #    #  #
+#  # #    #  #
+ ##  #### #  #
+
+";
+        assert_eq!(recognise_text(text), Ok("CEH".to_string()));
+        let grid = drawing(text).unwrap();
+        assert_eq!(recognise(&grid), Ok("CEH".to_string()));
+    }
+
+    #[test]
+    fn errors() {
+        let text = ".##..###.
+#..#.#..#
+#..#.###.
+####.#.##
+#..#.#..#
+#..#.###.";
+        assert_eq!(
+            recognise_text(text),
+            Err(OcrError::Unknown {
+                text: "A?".to_string(),
+                columns: vec![5]
+            })
+        );
+        assert_eq!(
+            recognise_text("#\n#\n#").unwrap_err().to_string(),
+            "letters are 3 cells high, expected 6 or 10"
+        );
+        assert!(matches!(recognise_text("#x"), Err(OcrError::Grid(_))));
+        assert_eq!(recognise_text("...\n..."), Ok(String::new()));
+    }
+}
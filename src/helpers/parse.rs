@@ -0,0 +1,200 @@
+use std::{any::Any, fmt::Display, ops::Range, str::FromStr};
+
+/// an error at a position in the input, printed with the offending line and a caret under the
+/// problem:
+///
+/// ```text
+/// line 2, column 3: invalid digit found in string
+///   2 | 2-x,6-8
+///     |   ^
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct ParseError {
+    /// numbered from 1.
+    pub line: usize,
+    /// numbered from 1, in characters.
+    pub column: usize,
+    /// the characters to underline, at least 1.
+    pub len: usize,
+    /// the whole line the error is on.
+    pub text: String,
+    pub cause: String,
+}
+
+/// the closest char boundary at or before `index`, or the length of `text` past its end.
+fn floor_char_boundary(text: &str, index: usize) -> usize {
+    (0..=index.min(text.len()))
+        .rev()
+        .find(|&i| text.is_char_boundary(i))
+        .unwrap_or(0)
+}
+
+impl ParseError {
+    /// an error at byte `offset` of `text`, which may span several lines.
+    pub fn new(text: &str, offset: usize, cause: impl Display) -> Self {
+        ParseError::spanning(text, offset..offset, cause)
+    }
+
+    /// an error underlining the bytes `range` of `text`, up to the end of the line it starts on.
+    /// offsets inside a character move back to its start.
+    pub fn spanning(text: &str, range: Range<usize>, cause: impl Display) -> Self {
+        let start = floor_char_boundary(text, range.start);
+        let line_start = text[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = text[start..].find('\n').map_or(text.len(), |i| start + i);
+        let end = floor_char_boundary(text, range.end.clamp(start, line_end));
+        ParseError {
+            line: text[..start].matches('\n').count() + 1,
+            column: text[line_start..start].chars().count() + 1,
+            len: text[start..end].chars().count().max(1),
+            text: text[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+            cause: cause.to_string(),
+        }
+    }
+
+    /// an error about all of `text`.
+    pub fn whole(text: &str, cause: impl Display) -> Self {
+        ParseError::spanning(text, 0..text.len(), cause)
+    }
+
+    /// moves the error down by `lines`, for text that started on a later line of the input.
+    pub fn shift_lines(mut self, lines: usize) -> Self {
+        self.line += lines;
+        self
+    }
+
+    /// the error of parsing `line` with `FromStr`. a `ParseError` keeps its position, other
+    /// errors point at the whole line.
+    pub fn from_error<E: Display + 'static>(line: &str, error: E) -> Self {
+        match (&error as &dyn Any).downcast_ref::<ParseError>() {
+            Some(error) => error.clone(),
+            None => ParseError::whole(line, error),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let number = self.line.to_string();
+        writeln!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.cause
+        )?;
+        writeln!(f, "  {} | {}", number, self.text)?;
+        write!(
+            f,
+            "  {} | {}{}",
+            " ".repeat(number.len()),
+            " ".repeat(self.column - 1),
+            "^".repeat(self.len)
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// every error found while parsing an input.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct ParseErrors(pub Vec<ParseError>);
+
+impl Display for ParseErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, error) in self.0.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", error)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseErrors {}
+
+/// parses every line of `input` with `FromStr`, collecting the errors of all lines that fail.
+pub fn parse_lines<T>(input: &str) -> Result<Vec<T>, ParseErrors>
+where
+    T: FromStr,
+    T::Err: Display + 'static,
+{
+    let mut values = Vec::new();
+    let mut errors = Vec::new();
+    for (index, line) in input.lines().enumerate() {
+        match line.parse() {
+            Ok(value) => values.push(value),
+            Err(error) => errors.push(ParseError::from_error(line, error).shift_lines(index)),
+        }
+    }
+    if errors.is_empty() {
+        Ok(values)
+    } else {
+        Err(ParseErrors(errors))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct Pair(u8, u8);
+
+    impl FromStr for Pair {
+        type Err = ParseError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let (a, b) = s
+                .split_once(',')
+                .ok_or_else(|| ParseError::whole(s, "expected ','"))?;
+            let number = |text: &str, offset: usize| {
+                text.parse()
+                    .map_err(|e| ParseError::spanning(s, offset..offset + text.len(), e))
+            };
+            Ok(Pair(number(a, 0)?, number(b, a.len() + 1)?))
+        }
+    }
+
+    #[test]
+    fn positions() {
+        let error = ParseError::new("ab\ncdé\nfg", 5, "bad");
+        assert_eq!((error.line, error.column, error.len), (2, 3, 1));
+        assert_eq!(error.text, "cdé");
+
+        let error = ParseError::spanning("12 345 6", 3..6, "too long");
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 4: too long\n  1 | 12 345 6\n    |    ^^^"
+        );
+        assert_eq!(ParseError::whole("", "empty").len, 1);
+
+        // the end of a CRLF line and the middle of a character
+        let error = ParseError::new("ab\r\ncd", 3, "x");
+        assert_eq!((error.line, error.column, error.len), (1, 4, 1));
+        assert_eq!(error.text, "ab");
+        let error = ParseError::spanning("aé b", 2..4, "x");
+        assert_eq!((error.column, error.len), (2, 2));
+        assert_eq!(ParseError::new("ab", 7, "x").column, 3);
+    }
+
+    #[test]
+    fn parse_all_lines() {
+        assert_eq!(parse_lines::<u8>("1\n2\n").unwrap_or_default(), vec![1, 2]);
+        let pairs = parse_lines::<Pair>("1,2\n").unwrap();
+        assert_eq!((pairs[0].0, pairs[0].1), (1, 2));
+
+        let errors = parse_lines::<Pair>("1,2\n3;4\n5,x\n").unwrap_err().0;
+        assert_eq!(errors.len(), 2);
+        assert_eq!((errors[0].line, errors[0].column, errors[0].len), (2, 1, 3));
+        assert_eq!((errors[1].line, errors[1].column, errors[1].len), (3, 3, 1));
+        assert_eq!(errors[1].cause, "invalid digit found in string");
+
+        let errors = parse_lines::<u8>("1\n\n300\n").unwrap_err();
+        assert_eq!(
+            errors.to_string(),
+            "line 2, column 1: cannot parse integer from empty string\n  2 | \n    | ^\n\
+             line 3, column 1: number too large to fit in target type\n  3 | 300\n    | ^^^"
+        );
+    }
+}
@@ -0,0 +1,90 @@
+//! runtime support of `advent_of_code::scan!`.
+use std::{fmt::Display, str::FromStr};
+
+use super::combinator::{Cursor, Failure, Parser};
+
+/// a hole of a `scan!` pattern: the text up to the first `terminator` on the line, or the rest of
+/// the line for an empty `terminator`, parsed with `FromStr`.
+pub fn hole<T>(terminator: &'static str) -> impl Parser<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    move |c: &mut Cursor<'_>| {
+        let rest = c.rest();
+        let line = rest.lines().next().unwrap_or("").trim_end_matches('\r');
+        let len = if terminator.is_empty() {
+            line.len()
+        } else {
+            line.find(terminator).ok_or_else(|| {
+                // point at the first character that stops matching: the end of the longest
+                // prefix that is a value, or the start of the hole if there is none
+                let matched = line
+                    .char_indices()
+                    .map(|(i, _)| i)
+                    .chain([line.len()])
+                    .rev()
+                    .find(|&i| i > 0 && line[..i].parse::<T>().is_ok())
+                    .unwrap_or(0);
+                Failure::expected(c.offset() + matched, format!("{:?}", terminator))
+            })?
+        };
+        let value = rest[..len]
+            .parse()
+            .map_err(|e| Failure::message(c.offset(), len, e))?;
+        c.advance(len);
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scan;
+
+    #[test]
+    fn scan_holes() {
+        assert_eq!(
+            scan!("move 3 from 1 to 2", "move {usize} from {usize} to {usize}"),
+            Ok((3, 1, 2))
+        );
+        assert_eq!(scan!("2-4,6-8\n", "{u8}-{u8},{u8}-{u8}"), Ok((2, 4, 6, 8)));
+        assert_eq!(scan!("x=-5", "x={i32}"), Ok(-5));
+        assert_eq!(
+            scan!("{a}: b c", "{{{char}}}: {String}"),
+            Ok(('a', "b c".to_string()))
+        );
+        assert_eq!(scan!("done", "done"), Ok(()));
+    }
+
+    #[test]
+    fn scan_errors() {
+        let error =
+            scan!("move 3 from x to 2", "move {usize} from {usize} to {usize}").unwrap_err();
+        assert_eq!((error.column, error.len), (13, 1));
+        assert_eq!(error.cause, "invalid digit found in string");
+
+        let error =
+            scan!("move 3 form 1 to 2", "move {usize} from {usize} to {usize}").unwrap_err();
+        assert_eq!(
+            (error.column, error.cause.as_str()),
+            (7, "expected \" from \"")
+        );
+
+        let error = scan!("a=12;b=3", "a={u8},b={u8}").unwrap_err();
+        assert_eq!(
+            (error.column, error.cause.as_str()),
+            (5, "expected \",b=\"")
+        );
+        let error = scan!("a=x;b=3", "a={u8},b={u8}").unwrap_err();
+        assert_eq!(error.column, 3);
+
+        let error = scan!("Move 3", "move {usize}").unwrap_err();
+        assert_eq!(
+            (error.column, error.cause.as_str()),
+            (1, "expected \"move \"")
+        );
+
+        let error = scan!("1-2,3-4 ", "{u8}-{u8},{u8}-{u8}").unwrap_err();
+        assert_eq!((error.column, error.len), (7, 2));
+    }
+}
@@ -0,0 +1,339 @@
+//! shortest paths over graphs given by a neighbour function. nodes can be anything hashable,
+//! e.g. grid positions or `(position, direction)` states:
+//!
+//! ```
+//! use advent_of_code::helpers::{grid::Grid, search::bfs};
+//!
+//! let grid = Grid::parse("..#\n#..\n").unwrap();
+//! let path = bfs(
+//!     (0, 0),
+//!     |&p| grid.neighbours_4(p).filter(|(_, &c)| c == '.').map(|(q, _)| q).collect::<Vec<_>>(),
+//!     |&p| p == (2, 1),
+//! );
+//! assert_eq!(path.map(|path| path.cost), Some(3));
+//! ```
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/// a shortest path and its cost. `nodes` goes from the start to the goal, both included.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Path<N, C> {
+    pub cost: C,
+    pub nodes: Vec<N>,
+}
+
+/// every node found by a search with its cost and the node it was reached from.
+struct Visited<N, C> {
+    nodes: Vec<(N, C, Option<usize>)>,
+    index: HashMap<N, usize>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Visited<N, C> {
+    fn new() -> Self {
+        Visited {
+            nodes: Vec::new(),
+            index: HashMap::new(),
+        }
+    }
+
+    /// records `node`, returning its index if it was not seen before.
+    fn insert(&mut self, node: N, cost: C, parent: Option<usize>) -> Option<usize> {
+        match self.index.entry(node.clone()) {
+            Entry::Occupied(_) => None,
+            Entry::Vacant(entry) => {
+                entry.insert(self.nodes.len());
+                self.nodes.push((node, cost, parent));
+                Some(self.nodes.len() - 1)
+            }
+        }
+    }
+
+    fn path(&self, end: usize) -> Path<N, C> {
+        let mut nodes = Vec::new();
+        let mut current = Some(end);
+        while let Some(i) = current {
+            nodes.push(self.nodes[i].0.clone());
+            current = self.nodes[i].2;
+        }
+        nodes.reverse();
+        Path {
+            cost: self.nodes[end].1,
+            nodes,
+        }
+    }
+
+    fn into_costs(self) -> HashMap<N, C> {
+        self.nodes
+            .into_iter()
+            .map(|(node, cost, _)| (node, cost))
+            .collect()
+    }
+}
+
+/// the shortest path from `start` to a node for which `is_goal` holds, counting steps.
+pub fn bfs<N, I>(
+    start: N,
+    neighbours: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, usize>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    bfs_multi([start], neighbours, is_goal)
+}
+
+/// like `bfs`, starting from whichever of `starts` is closest to a goal.
+pub fn bfs_multi<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, usize>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let (visited, goal) = breadth_first(starts, &mut neighbours, &mut is_goal);
+    goal.map(|goal| visited.path(goal))
+}
+
+/// the number of steps from the closest of `starts` to every reachable node.
+pub fn bfs_reachable<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    breadth_first(starts, &mut neighbours, &mut |_| false)
+        .0
+        .into_costs()
+}
+
+fn breadth_first<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: &mut impl FnMut(&N) -> I,
+    is_goal: &mut impl FnMut(&N) -> bool,
+) -> (Visited<N, usize>, Option<usize>)
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut visited = Visited::new();
+    let mut queue = starts
+        .into_iter()
+        .filter_map(|start| visited.insert(start, 0, None))
+        .collect::<VecDeque<_>>();
+    while let Some(i) = queue.pop_front() {
+        if is_goal(&visited.nodes[i].0) {
+            return (visited, Some(i));
+        }
+        let steps = visited.nodes[i].1 + 1;
+        for next in neighbours(&visited.nodes[i].0) {
+            queue.extend(visited.insert(next, steps, Some(i)));
+        }
+    }
+    (visited, None)
+}
+
+/// the cheapest path from `start` to a node for which `is_goal` holds. `neighbours` returns the
+/// next nodes with the cost of moving to them, which must not be negative.
+pub fn dijkstra<N, C, I>(
+    start: N,
+    neighbours: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    dijkstra_multi([start], neighbours, is_goal)
+}
+
+/// like `dijkstra`, starting from whichever of `starts` is cheapest.
+pub fn dijkstra_multi<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let (visited, goal) = best_first(starts, &mut neighbours, &mut |_| C::default(), &mut is_goal);
+    goal.map(|goal| visited.path(goal))
+}
+
+/// the cost of the cheapest path from the closest of `starts` to every reachable node.
+pub fn dijkstra_reachable<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+) -> HashMap<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    best_first(starts, &mut neighbours, &mut |_| C::default(), &mut |_| {
+        false
+    })
+    .0
+    .into_costs()
+}
+
+/// like `dijkstra`, exploring the nodes that `heuristic` estimates to be closest to a goal first.
+/// the path is the cheapest one if the heuristic never overestimates the remaining cost and never
+/// drops by more than the cost of a step, like the manhattan distance on a grid.
+pub fn astar<N, C, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let (visited, goal) = best_first([start], &mut neighbours, &mut heuristic, &mut is_goal);
+    goal.map(|goal| visited.path(goal))
+}
+
+fn best_first<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: &mut impl FnMut(&N) -> I,
+    heuristic: &mut impl FnMut(&N) -> C,
+    is_goal: &mut impl FnMut(&N) -> bool,
+) -> (Visited<N, C>, Option<usize>)
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut visited = Visited::new();
+    let mut heap = BinaryHeap::new();
+    for start in starts {
+        let estimate = heuristic(&start);
+        if let Some(i) = visited.insert(start, C::default(), None) {
+            heap.push(Reverse((estimate, C::default(), i)));
+        }
+    }
+    while let Some(Reverse((_, cost, i))) = heap.pop() {
+        // a cheaper way to this node was found after this entry was queued
+        if cost > visited.nodes[i].1 {
+            continue;
+        }
+        if is_goal(&visited.nodes[i].0) {
+            return (visited, Some(i));
+        }
+        for (next, step) in neighbours(&visited.nodes[i].0) {
+            let next_cost = cost + step;
+            let j = match visited.index.get(&next) {
+                Some(&j) if next_cost < visited.nodes[j].1 => {
+                    visited.nodes[j].1 = next_cost;
+                    visited.nodes[j].2 = Some(i);
+                    j
+                }
+                Some(_) => continue,
+                None => visited.insert(next, next_cost, Some(i)).unwrap(),
+            };
+            let estimate = next_cost + heuristic(&visited.nodes[j].0);
+            heap.push(Reverse((estimate, next_cost, j)));
+        }
+    }
+    (visited, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::grid::Grid;
+
+    const MAZE: &str = "\
+S..#....
+.#.#.##.
+.#...#E.
+.####.#.
+......#.
+";
+
+    fn open(grid: &Grid<char>, p: (usize, usize)) -> Vec<(usize, usize)> {
+        grid.neighbours_4(p)
+            .filter(|(_, &c)| c != '#')
+            .map(|(q, _)| q)
+            .collect()
+    }
+
+    fn find(grid: &Grid<char>, target: char) -> (usize, usize) {
+        grid.iter().find(|(_, &c)| c == target).unwrap().0
+    }
+
+    #[test]
+    fn breadth_first_search() {
+        let grid = Grid::parse(MAZE).unwrap();
+        let (start, end) = (find(&grid, 'S'), find(&grid, 'E'));
+        let path = bfs(start, |&p| open(&grid, p), |&p| p == end).unwrap();
+        assert_eq!(path.cost, 14);
+        assert_eq!(path.nodes.len(), 15);
+        assert_eq!((path.nodes[0], path.nodes[14]), (start, end));
+        assert!(path
+            .nodes
+            .windows(2)
+            .all(|w| open(&grid, w[0]).contains(&w[1])));
+
+        assert_eq!(
+            bfs(start, |&p| open(&grid, p), |&p| p == (7, 0)).map(|p| p.cost),
+            Some(11)
+        );
+        assert_eq!(bfs(start, |&p| open(&grid, p), |_| false), None);
+
+        // of the cells in the bottom row, the one in the last column is closest to the goal
+        let bottom = (0..grid.width())
+            .map(|x| (x, 4))
+            .filter(|&p| grid[p] != '#');
+        let path = bfs_multi(bottom, |&p| open(&grid, p), |&p| p == end).unwrap();
+        assert_eq!((path.cost, path.nodes[0]), (3, (7, 4)));
+
+        let reachable = bfs_reachable([start], |&p| open(&grid, p));
+        assert_eq!(
+            reachable.len(),
+            grid.iter().filter(|(_, &c)| c != '#').count()
+        );
+        assert_eq!(reachable[&end], 14);
+    }
+
+    #[test]
+    fn weighted_search() {
+        let grid = Grid::parse_with("1163\n1381\n2136\n3694\n", |c| c.to_digit(10)).unwrap();
+        let end = (3, 3);
+        let neighbours = |&p: &(usize, usize)| {
+            grid.neighbours_4(p)
+                .map(|(q, &risk)| (q, risk))
+                .collect::<Vec<_>>()
+        };
+        let path = dijkstra((0, 0), neighbours, |&p| p == end).unwrap();
+        assert_eq!(path.cost, 17);
+        assert_eq!(
+            path.cost,
+            path.nodes[1..].iter().map(|&p| grid[p]).sum::<u32>()
+        );
+
+        let manhattan = |&(x, y): &(usize, usize)| (end.0 - x + end.1 - y) as u32;
+        let path = astar((0, 0), neighbours, manhattan, |&p| p == end).unwrap();
+        assert_eq!(path.cost, 17);
+
+        let path = dijkstra_multi([(0, 0), (3, 0)], neighbours, |&p| p == end).unwrap();
+        assert_eq!((path.cost, path.nodes[0]), (11, (3, 0)));
+
+        let costs = dijkstra_reachable([(0, 0)], neighbours);
+        assert_eq!(costs.len(), 16);
+        assert_eq!((costs[&(0, 0)], costs[&(3, 3)]), (0, 17));
+    }
+}
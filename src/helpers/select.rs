@@ -0,0 +1,174 @@
+use std::{cmp::Reverse, collections::BinaryHeap, ops::Add};
+
+/// the `k` largest values, largest first. keeps only `k` values at a time instead of sorting all
+/// of them.
+pub fn top_k<T: Ord>(values: impl IntoIterator<Item = T>, k: usize) -> Vec<T> {
+    // a min-heap of the largest values so far, so the smallest of them can be replaced
+    let mut heap = BinaryHeap::with_capacity(k + 1);
+    for value in values {
+        heap.push(Reverse(value));
+        if heap.len() > k {
+            heap.pop();
+        }
+    }
+    heap.into_sorted_vec().into_iter().map(|r| r.0).collect()
+}
+
+/// the `k` smallest values, smallest first. keeps only `k` values at a time instead of sorting
+/// all of them.
+pub fn k_smallest<T: Ord>(values: impl IntoIterator<Item = T>, k: usize) -> Vec<T> {
+    let mut heap = BinaryHeap::with_capacity(k + 1);
+    for value in values {
+        heap.push(value);
+        if heap.len() > k {
+            heap.pop();
+        }
+    }
+    heap.into_sorted_vec()
+}
+
+/// numbers that `Stats` can add up and average.
+pub trait Number: Ord + Copy + Default + Add<Output = Self> {
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! number_impls {
+    ($($t:ty),+) => {$(
+        impl Number for $t {
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+        }
+    )+};
+}
+
+number_impls!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// collects values one at a time and describes them. count, minimum and maximum work for any
+/// ordered values and are kept up to date as values are pushed. the values themselves are kept
+/// for `median` and `nth_smallest`, and numbers can be summed and averaged from them.
+#[derive(Clone, Debug)]
+pub struct Stats<T> {
+    values: Vec<T>,
+    min: Option<T>,
+    max: Option<T>,
+}
+
+impl<T> Default for Stats<T> {
+    fn default() -> Self {
+        Stats {
+            values: Vec::new(),
+            min: None,
+            max: None,
+        }
+    }
+}
+
+impl<T: Ord + Clone> Stats<T> {
+    pub fn new() -> Self {
+        Stats::default()
+    }
+
+    pub fn push(&mut self, value: T) {
+        if self.min.as_ref().is_none_or(|min| value < *min) {
+            self.min = Some(value.clone());
+        }
+        if self.max.as_ref().is_none_or(|max| value > *max) {
+            self.max = Some(value.clone());
+        }
+        self.values.push(value);
+    }
+
+    pub fn count(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn min(&self) -> Option<&T> {
+        self.min.as_ref()
+    }
+
+    pub fn max(&self) -> Option<&T> {
+        self.max.as_ref()
+    }
+
+    /// the value that would be at `index` if the values were sorted, found without sorting them.
+    /// reorders the stored values, in time linear in their count.
+    pub fn nth_smallest(&mut self, index: usize) -> Option<&T> {
+        if index >= self.values.len() {
+            return None;
+        }
+        Some(self.values.select_nth_unstable(index).1)
+    }
+
+    /// the middle value, or the lower of the two middle values for an even count.
+    pub fn median(&mut self) -> Option<&T> {
+        self.nth_smallest(self.values.len().saturating_sub(1) / 2)
+    }
+}
+
+impl<T: Number> Stats<T> {
+    pub fn sum(&self) -> T {
+        self.values
+            .iter()
+            .fold(T::default(), |sum, &value| sum + value)
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        let total: f64 = self.values.iter().map(|&value| value.to_f64()).sum();
+        (!self.values.is_empty()).then(|| total / self.values.len() as f64)
+    }
+}
+
+impl<T: Ord + Clone> Extend<T> for Stats<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, values: I) {
+        for value in values {
+            self.push(value);
+        }
+    }
+}
+
+impl<T: Ord + Clone> FromIterator<T> for Stats<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(values: I) -> Self {
+        let mut stats = Stats::new();
+        stats.extend(values);
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selection() {
+        let values = [5, 1, 9, 3, 7, 9, 2];
+        assert_eq!(top_k(values, 3), vec![9, 9, 7]);
+        assert_eq!(k_smallest(values, 2), vec![1, 2]);
+        assert_eq!(top_k(values, 10).len(), 7);
+        assert_eq!(top_k(values, 0), Vec::<i32>::new());
+        assert_eq!(top_k(["b", "c", "a"], 1), vec!["c"]);
+    }
+
+    #[test]
+    fn statistics() {
+        let mut stats = [5u64, 1, 9, 3, 7, 2].into_iter().collect::<Stats<_>>();
+        assert_eq!(stats.count(), 6);
+        assert_eq!((stats.min(), stats.max()), (Some(&1), Some(&9)));
+        assert_eq!(stats.sum(), 27);
+        assert_eq!(stats.mean(), Some(4.5));
+        assert_eq!(stats.median(), Some(&3));
+        stats.push(4);
+        assert_eq!(stats.median(), Some(&4));
+        assert_eq!(stats.nth_smallest(6), Some(&9));
+        assert_eq!(stats.nth_smallest(7), None);
+
+        let mut empty = Stats::<i32>::new();
+        assert_eq!((empty.min(), empty.mean(), empty.sum()), (None, None, 0));
+        assert_eq!(empty.median(), None);
+
+        let words = ["pear", "apple", "fig"].into_iter().collect::<Stats<_>>();
+        assert_eq!(words.min(), Some(&"apple"));
+        let points = [(2, 'b'), (1, 'z')].into_iter().collect::<Stats<_>>();
+        assert_eq!(points.max(), Some(&(2, 'b')));
+    }
+}
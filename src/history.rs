@@ -0,0 +1,269 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    process::Command,
+    sync::OnceLock,
+    time::Duration,
+};
+
+use crate::{
+    clock::{self, Clock, SystemClock},
+    config,
+};
+
+const HEADER: &str = "# timestamp\tday\tpart\tanswer\tnanoseconds\tcommit";
+
+/// one result printed by `solve!`.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Entry {
+    /// unix timestamp of the run.
+    pub timestamp: u64,
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub elapsed: Duration,
+    /// short hash of `HEAD`, suffixed with `-dirty` if tracked files were modified.
+    pub commit: Option<String>,
+}
+
+impl Entry {
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{:02}\t{}\t{}\t{}\t{}",
+            self.timestamp,
+            self.day,
+            self.part,
+            self.answer.replace(['\t', '\n', '\r'], " "),
+            self.elapsed.as_nanos(),
+            self.commit.as_deref().unwrap_or("-")
+        )
+    }
+
+    fn from_line(line: &str) -> Option<Self> {
+        let fields = line.split('\t').collect::<Vec<_>>();
+        if let [timestamp, day, part, answer, nanos, commit] = fields[..] {
+            Some(Entry {
+                timestamp: timestamp.parse().ok()?,
+                day: day.parse().ok()?,
+                part: part.parse().ok()?,
+                answer: answer.to_string(),
+                elapsed: Duration::from_nanos(nanos.parse().ok()?),
+                commit: (commit != "-").then(|| commit.to_string()),
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// parses the history, skipping lines it does not understand.
+pub fn parse(text: &str) -> Vec<Entry> {
+    text.lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(Entry::from_line)
+        .collect()
+}
+
+pub fn load(path: &Path) -> Result<Vec<Entry>, io::Error> {
+    match fs::read_to_string(path) {
+        Ok(text) => Ok(parse(&text)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e),
+    }
+}
+
+pub fn append(path: &Path, entry: &Entry) -> Result<(), io::Error> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    let is_new = !path.exists();
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    if is_new {
+        writeln!(file, "{}", HEADER)?;
+    }
+    writeln!(file, "{}", entry.to_line())
+}
+
+/// the commit the working tree is on, if this is a git checkout. looked up once per process.
+pub fn git_commit() -> Option<String> {
+    static COMMIT: OnceLock<Option<String>> = OnceLock::new();
+    COMMIT.get_or_init(read_git_commit).clone()
+}
+
+fn read_git_commit() -> Option<String> {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    let commit = git(&["rev-parse", "--short", "HEAD"])?;
+    let dirty = git(&["status", "--porcelain", "--untracked-files=no"])
+        .is_some_and(|status| !status.is_empty());
+    Some(if dirty {
+        format!("{}-dirty", commit)
+    } else {
+        commit
+    })
+}
+
+/// appends a result to the history. failing to write the history never fails a solution.
+pub fn record(day: u8, part: u8, answer: &str, elapsed: Duration) {
+    let entry = Entry {
+        timestamp: SystemClock.now(),
+        day,
+        part,
+        answer: answer.to_string(),
+        elapsed,
+        commit: git_commit(),
+    };
+    append(&config::get().paths.history, &entry).ok();
+}
+
+/// the latest results of a day on one commit.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Revision {
+    pub commit: Option<String>,
+    /// time of the last run on this commit.
+    pub timestamp: u64,
+    pub runs: usize,
+    pub parts: [Option<(String, Duration)>; 2],
+}
+
+/// groups the results of `day` by commit. runs on the same commit in a row are merged, keeping
+/// the latest answer and time of each part.
+pub fn revisions(entries: &[Entry], day: u8) -> Vec<Revision> {
+    let mut revisions: Vec<Revision> = Vec::new();
+    for entry in entries.iter().filter(|e| e.day == day) {
+        let index = usize::from(entry.part.clamp(1, 2) - 1);
+        let result = Some((entry.answer.clone(), entry.elapsed));
+        match revisions.last_mut() {
+            Some(revision) if revision.commit == entry.commit => {
+                revision.timestamp = entry.timestamp;
+                revision.runs += 1;
+                revision.parts[index] = result;
+            }
+            _ => {
+                let mut parts = [None, None];
+                parts[index] = result;
+                revisions.push(Revision {
+                    commit: entry.commit.clone(),
+                    timestamp: entry.timestamp,
+                    runs: 1,
+                    parts,
+                });
+            }
+        }
+    }
+    revisions
+}
+
+/// one row per revision. answers that differ from the previous revision are marked with `!`.
+pub fn render(revisions: &[Revision]) -> String {
+    let mut out = format!(
+        "{:<14}  {:<23}  {:<24}  part 2\n",
+        "commit", "last run", "part 1"
+    );
+    let mut previous: [Option<&str>; 2] = [None, None];
+    for revision in revisions {
+        let parts = [0, 1].map(|i| match &revision.parts[i] {
+            Some((answer, elapsed)) => {
+                let changed = previous[i].is_some_and(|p| p != answer);
+                previous[i] = Some(answer);
+                format!(
+                    "{}{} ({:.2?})",
+                    answer,
+                    if changed { " !" } else { "" },
+                    elapsed
+                )
+            }
+            None => "-".into(),
+        });
+        out.push_str(
+            format!(
+                "{:<14}  {:<23}  {:<24}  {:<24}",
+                revision.commit.as_deref().unwrap_or("(no git)"),
+                clock::format_timestamp(revision.timestamp),
+                parts[0],
+                parts[1]
+            )
+            .trim_end(),
+        );
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(timestamp: u64, part: u8, answer: &str, micros: u64, commit: &str) -> Entry {
+        Entry {
+            timestamp,
+            day: 1,
+            part,
+            answer: answer.into(),
+            elapsed: Duration::from_micros(micros),
+            commit: Some(commit.into()),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let entries = [
+            entry(1669870800, 1, "24000", 14, "a1b2c3d"),
+            Entry {
+                commit: None,
+                answer: "tab\there".into(),
+                ..entry(1669870900, 2, "", 10, "")
+            },
+        ];
+        let text = entries
+            .iter()
+            .map(|e| format!("{}\n", e.to_line()))
+            .collect::<String>();
+        let parsed = parse(&format!("{}\n{}garbage\n", HEADER, text));
+
+        assert_eq!(parsed[0], entries[0]);
+        assert_eq!(parsed[1].answer, "tab here");
+        assert_eq!(parsed[1].commit, None);
+        assert_eq!(parsed.len(), 2);
+    }
+
+    #[test]
+    fn group_by_commit() {
+        let entries = vec![
+            entry(100, 1, "24000", 20, "a1b2c3d"),
+            entry(101, 2, "45000", 20, "a1b2c3d"),
+            entry(200, 1, "24000", 15, "a1b2c3d"),
+            entry(300, 1, "24000", 10, "e4f5a6b"),
+            entry(301, 2, "45001", 12, "e4f5a6b"),
+            Entry {
+                day: 2,
+                ..entry(400, 1, "15", 1, "e4f5a6b")
+            },
+        ];
+        let revisions = revisions(&entries, 1);
+
+        assert_eq!(revisions.len(), 2);
+        assert_eq!(revisions[0].runs, 3);
+        assert_eq!(revisions[0].timestamp, 200);
+        assert_eq!(
+            revisions[0].parts[0],
+            Some(("24000".into(), Duration::from_micros(15)))
+        );
+
+        let rendered = render(&revisions);
+        let lines = rendered.lines().collect::<Vec<_>>();
+        assert!(lines[1].starts_with("a1b2c3d         1970-01-01 00:03:20 UTC  24000 (15.00µs)"));
+        assert!(lines[2].ends_with("24000 (10.00µs)           45001 ! (12.00µs)"));
+    }
+}
@@ -0,0 +1,417 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::{collections::BTreeMap, fmt::Display};
+
+use crate::{
+    clock,
+    json::{self, JsonError, Value},
+};
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum LeaderboardError {
+    Json(JsonError),
+    /// valid JSON that does not look like a private leaderboard.
+    Format(String),
+}
+
+impl Display for LeaderboardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LeaderboardError::Json(e) => write!(f, "{}", e),
+            LeaderboardError::Format(message) => {
+                write!(f, "not a private leaderboard: {}", message)
+            }
+        }
+    }
+}
+
+/// the number of days with puzzles, one column each in the star strip.
+const DAYS: u8 = 25;
+
+/// one star of a member. ordered like the website orders stars: by time, then by `index` for
+/// stars within the same second.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub struct Star {
+    /// unix timestamp.
+    pub at: u64,
+    /// the `star_index` of the star, 0 if the leaderboard does not have one.
+    pub index: u64,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Member {
+    pub id: u64,
+    /// `None` for anonymous users.
+    pub name: Option<String>,
+    pub stars: u32,
+    /// every star, keyed by `(day, part)`.
+    pub completions: BTreeMap<(u8, u8), Star>,
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    pub fn completion(&self, day: u8, part: u8) -> Option<Star> {
+        self.completions.get(&(day, part)).copied()
+    }
+
+    /// unix timestamp of a star.
+    pub fn star(&self, day: u8, part: u8) -> Option<u64> {
+        self.completion(day, part).map(|star| star.at)
+    }
+
+    /// seconds between the first and the second star of a day. `None` if a star is missing or
+    /// the timestamps are out of order.
+    pub fn second_star_delta(&self, day: u8) -> Option<u64> {
+        self.star(day, 2)?.checked_sub(self.star(day, 1)?)
+    }
+}
+
+/// a private leaderboard in the JSON format served at
+/// `https://adventofcode.com/{year}/leaderboard/private/view/{id}.json`.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Leaderboard {
+    pub event: Option<u16>,
+    pub members: Vec<Member>,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Ranking<'a> {
+    pub rank: usize,
+    pub member: &'a Member,
+    pub score: u64,
+}
+
+fn parse_member(id: &str, value: &Value) -> Result<Member, LeaderboardError> {
+    let bad =
+        |field: &str| LeaderboardError::Format(format!("member {} has no valid {}", id, field));
+    let mut completions = BTreeMap::new();
+    if let Some(days) = value.get("completion_day_level").and_then(Value::as_object) {
+        for (day, parts) in days {
+            let day = day.parse().map_err(|_| bad("completion day"))?;
+            for (part, star) in parts.as_object().ok_or_else(|| bad("completion day"))? {
+                let part = part.parse().map_err(|_| bad("completion part"))?;
+                let at = star
+                    .get("get_star_ts")
+                    .and_then(Value::as_u64)
+                    .ok_or_else(|| bad("get_star_ts"))?;
+                let index = star.get("star_index").and_then(Value::as_u64).unwrap_or(0);
+                completions.insert((day, part), Star { at, index });
+            }
+        }
+    }
+
+    Ok(Member {
+        id: value
+            .get("id")
+            .and_then(Value::as_u64)
+            .or_else(|| id.parse().ok())
+            .ok_or_else(|| bad("id"))?,
+        name: value.get("name").and_then(Value::as_str).map(String::from),
+        stars: value
+            .get("stars")
+            .and_then(Value::as_u64)
+            .map_or(completions.len() as u32, |stars| stars as u32),
+        completions,
+    })
+}
+
+impl Leaderboard {
+    pub fn parse(text: &str) -> Result<Self, LeaderboardError> {
+        let root = json::parse(text).map_err(LeaderboardError::Json)?;
+        let members = root
+            .get("members")
+            .and_then(Value::as_object)
+            .ok_or_else(|| LeaderboardError::Format("missing \"members\"".into()))?
+            .iter()
+            .map(|(id, member)| parse_member(id, member))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Leaderboard {
+            event: root
+                .get("event")
+                .and_then(Value::as_u64)
+                .and_then(|year| u16::try_from(year).ok()),
+            members,
+        })
+    }
+
+    /// local score as computed by the website: for every star, the first member to get it
+    /// receives as many points as there are members, the second one point less, and so on. stars
+    /// within the same second go by their `star_index`.
+    pub fn local_scores(&self) -> BTreeMap<u64, u64> {
+        let mut scores = self
+            .members
+            .iter()
+            .map(|m| (m.id, 0))
+            .collect::<BTreeMap<_, _>>();
+        let count = self.members.len() as u64;
+
+        for day in 1..=DAYS {
+            for part in 1..=2 {
+                let mut finishers = self
+                    .members
+                    .iter()
+                    .filter_map(|m| Some((m.completion(day, part)?, m.id)))
+                    .collect::<Vec<_>>();
+                finishers.sort_unstable();
+                for (place, (_, id)) in finishers.into_iter().enumerate() {
+                    *scores.entry(id).or_default() += count - place as u64;
+                }
+            }
+        }
+        scores
+    }
+
+    /// members by descending local score. ties are broken by who got their last star first,
+    /// and members with the same score share a rank.
+    pub fn rankings(&self) -> Vec<Ranking<'_>> {
+        let scores = self.local_scores();
+        let mut members = self.members.iter().collect::<Vec<_>>();
+        members.sort_by_key(|m| {
+            let last_star = m.completions.values().max();
+            // members without stars come last
+            (
+                std::cmp::Reverse(scores[&m.id]),
+                last_star.is_none(),
+                last_star.copied(),
+                m.id,
+            )
+        });
+
+        let mut rankings: Vec<Ranking> = Vec::with_capacity(members.len());
+        for (i, member) in members.into_iter().enumerate() {
+            let score = scores[&member.id];
+            let rank = match rankings.last() {
+                Some(previous) if previous.score == score => previous.rank,
+                _ => i + 1,
+            };
+            rankings.push(Ranking {
+                rank,
+                member,
+                score,
+            });
+        }
+        rankings
+    }
+
+    /// the ranking table with a star strip for days 1 to 25: `★` both parts, `☆` part one only.
+    pub fn render_rankings(&self) -> String {
+        let mut out = format!(
+            "{:>3}  {:>5}  {:>5}  {}  {}\n",
+            "#",
+            "score",
+            "stars",
+            day_ruler(),
+            "name"
+        );
+        for ranking in self.rankings() {
+            let strip = (1..=DAYS)
+                .map(|day| {
+                    let member = ranking.member;
+                    match (member.star(day, 1), member.star(day, 2)) {
+                        (Some(_), Some(_)) => '★',
+                        (Some(_), None) => '☆',
+                        _ => '·',
+                    }
+                })
+                .collect::<String>();
+            out.push_str(&format!(
+                "{:>3}  {:>5}  {:>5}  {}  {}\n",
+                format!("{})", ranking.rank),
+                ranking.score,
+                ranking.member.stars,
+                strip,
+                ranking.member.display_name()
+            ));
+        }
+        out
+    }
+
+    /// when every member got the stars of `day`, relative to the unlock if the year is known,
+    /// with the time it took to get from the first to the second star.
+    pub fn render_day(&self, day: u8) -> String {
+        let unlock = self.event.map(|year| clock::unlock_time(year, day));
+        let when = |at: Option<u64>| match (at, unlock) {
+            (Some(at), Some(unlock)) => clock::format_countdown(at.saturating_sub(unlock)),
+            (Some(at), None) => clock::format_timestamp(at),
+            (None, _) => "-".into(),
+        };
+
+        let mut members = self
+            .members
+            .iter()
+            .filter(|m| m.star(day, 1).is_some())
+            .collect::<Vec<_>>();
+        members.sort_by_key(|m| {
+            let second = m.completion(day, 2);
+            (second.is_none(), second, m.completion(day, 1), m.id)
+        });
+
+        let mut out = format!(
+            "Day {}{}\n{:>12}  {:>12}  {:>12}  {}\n",
+            day,
+            if unlock.is_some() {
+                ", times since unlock"
+            } else {
+                ""
+            },
+            "part 1",
+            "part 2",
+            "delta",
+            "name"
+        );
+        if members.is_empty() {
+            out.push_str("nobody has a star yet.\n");
+        }
+        for member in members {
+            out.push_str(&format!(
+                "{:>12}  {:>12}  {:>12}  {}\n",
+                when(member.star(day, 1)),
+                when(member.star(day, 2)),
+                member
+                    .second_star_delta(day)
+                    .map_or("-".into(), clock::format_countdown),
+                member.display_name()
+            ));
+        }
+        out
+    }
+}
+
+/// a header for the star strip, one column per day with every fifth day numbered. the last digit
+/// of a number sits above its day.
+fn day_ruler() -> String {
+    let mut ruler = vec![' '; DAYS as usize];
+    for day in (0..=DAYS).step_by(5).map(|day| day.max(1)) {
+        let label = day.to_string();
+        let start = day as usize - label.len();
+        for (column, c) in ruler[start..].iter_mut().zip(label.chars()) {
+            *column = c;
+        }
+    }
+    ruler.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2022-12-01T05:00:00Z
+    const DAY_ONE: u64 = 1669870800;
+
+    fn leaderboard() -> Leaderboard {
+        let text = format!(
+            r#"{{
+                "event": "2022",
+                "owner_id": 1,
+                "members": {{
+                    "1": {{"id": 1, "name": "alice", "stars": 3, "local_score": 0,
+                        "completion_day_level": {{
+                            "1": {{"1": {{"get_star_ts": {a1}, "star_index": 10}},
+                                   "2": {{"get_star_ts": {a2}, "star_index": 30}}}},
+                            "2": {{"1": {{"get_star_ts": {a3}, "star_index": 50}}}}
+                        }}}},
+                    "2": {{"id": 2, "name": null, "stars": 2, "local_score": 0,
+                        "completion_day_level": {{
+                            "1": {{"1": {{"get_star_ts": {b1}, "star_index": 5}},
+                                   "2": {{"get_star_ts": {b2}, "star_index": 20}}}}
+                        }}}},
+                    "3": {{"id": 3, "name": "carol", "stars": 0, "local_score": 0,
+                        "completion_day_level": {{}}}}
+                }}
+            }}"#,
+            a1 = DAY_ONE + 300,
+            a2 = DAY_ONE + 900,
+            a3 = DAY_ONE + 86400 + 600,
+            b1 = DAY_ONE + 200,
+            b2 = DAY_ONE + 1000,
+        );
+        Leaderboard::parse(&text).unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let leaderboard = leaderboard();
+        assert_eq!(leaderboard.event, Some(2022));
+        assert_eq!(leaderboard.members.len(), 3);
+        let anonymous = &leaderboard.members[1];
+        assert_eq!(anonymous.display_name(), "(anonymous user #2)");
+        assert_eq!(anonymous.second_star_delta(1), Some(800));
+        assert_eq!(anonymous.second_star_delta(2), None);
+
+        // a hand-edited file with the stars out of order
+        let mut swapped = anonymous.clone();
+        swapped.completions.insert(
+            (1, 2),
+            Star {
+                at: DAY_ONE,
+                index: 1,
+            },
+        );
+        assert_eq!(swapped.second_star_delta(1), None);
+
+        assert!(matches!(
+            Leaderboard::parse("{}"),
+            Err(LeaderboardError::Format(_))
+        ));
+        assert!(matches!(
+            Leaderboard::parse("{"),
+            Err(LeaderboardError::Json(_))
+        ));
+    }
+
+    #[test]
+    fn local_score() {
+        let leaderboard = leaderboard();
+        // day 1 part 1: bob 3, alice 2. part 2: alice 3, bob 2. day 2 part 1: alice 3.
+        assert_eq!(
+            leaderboard.local_scores(),
+            BTreeMap::from([(1, 8), (2, 5), (3, 0)])
+        );
+
+        let rankings = leaderboard.rankings();
+        let order = rankings
+            .iter()
+            .map(|r| (r.rank, r.member.id, r.score))
+            .collect::<Vec<_>>();
+        assert_eq!(order, vec![(1, 1, 8), (2, 2, 5), (3, 3, 0)]);
+
+        // both stars in the same second: the lower star_index came first
+        let tied = Leaderboard::parse(
+            r#"{"members": {
+                "1": {"id": 1, "completion_day_level": {"1": {"1": {"get_star_ts": 60, "star_index": 7}}}},
+                "2": {"id": 2, "completion_day_level": {"1": {"1": {"get_star_ts": 60, "star_index": 3}}}}
+            }}"#,
+        )
+        .unwrap();
+        assert_eq!(tied.local_scores(), BTreeMap::from([(1, 1), (2, 2)]));
+        assert_eq!(tied.rankings()[0].member.id, 2);
+    }
+
+    #[test]
+    fn render() {
+        let leaderboard = leaderboard();
+        let rankings = leaderboard.render_rankings();
+        assert_eq!(day_ruler(), "1   5   10   15   20   25");
+        assert_eq!(day_ruler().len(), DAYS as usize);
+        assert!(rankings.contains(&format!(" 1)      8      3  ★☆{}  alice", "·".repeat(23))));
+
+        let day = leaderboard.render_day(1);
+        let lines = day.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], "Day 1, times since unlock");
+        assert_eq!(lines[2], "    00:05:00      00:15:00      00:10:00  alice");
+        assert_eq!(
+            lines[3],
+            "    00:03:20      00:16:40      00:13:20  (anonymous user #2)"
+        );
+        assert_eq!(lines.len(), 4);
+    }
+}
@@ -3,10 +3,28 @@
  * There is no need to edit this file unless you want to change template functionality.
  * Prefer `./helpers.rs` if you want to extract code from your solutions.
  */
-use std::env;
-use std::fs;
-
+pub mod answers;
+pub mod bench;
+pub mod calendar;
+pub mod clock;
+pub mod config;
+pub mod crypt;
+pub mod fuzz;
 pub mod helpers;
+pub mod history;
+pub mod json;
+pub mod leaderboard;
+pub mod manifest;
+pub mod puzzle;
+pub mod race;
+pub mod scaffold;
+pub mod shrink;
+pub mod timings;
+
+// lets `scan!` refer to this crate as `::advent_of_code` in its own tests, too.
+extern crate self as advent_of_code;
+
+pub use advent_of_code_macros::scan;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
@@ -14,12 +32,32 @@ pub const ANSI_RESET: &str = "\x1b[0m";
 
 #[macro_export]
 macro_rules! solve {
+    // several implementations of a part. the first one is the solution, the others are only run
+    // in a race with `--race`.
+    ($part:expr, $input:expr, [$($solver:path),+ $(,)?]) => {{
+        let implementations: &[advent_of_code::race::Implementation<_>] =
+            &[$((stringify!($solver), $solver)),+];
+        if let Some(part) = advent_of_code::shrink::checking() {
+            if part == $part {
+                advent_of_code::shrink::check(implementations, $input);
+            }
+        } else if advent_of_code::race::enabled() {
+            advent_of_code::race::run($part, implementations, $input);
+        } else {
+            let (_, solver) = implementations[0];
+            advent_of_code::solve!($part, solver, $input);
+        }
+    }};
     ($part:expr, $solver:ident, $input:expr) => {{
-        use advent_of_code::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+        use advent_of_code::{answers::Answers, config::OutputFormat, history};
         use std::fmt::Display;
-        use std::time::Instant;
+        use std::time::{Duration, Instant};
 
-        fn print_result<T: Display>(func: impl FnOnce(&str) -> Option<T>, input: &str) {
+        fn print_result<T: Display>(
+            func: impl FnOnce(&str) -> Option<T>,
+            input: &str,
+            output: OutputFormat,
+        ) -> Option<(String, Duration)> {
             let timer = Instant::now();
             let result = func(input);
             let elapsed = timer.elapsed();
@@ -27,27 +65,144 @@ macro_rules! solve {
                 Some(result) => {
                     println!(
                         "{} {}(elapsed: {:.2?}){}",
-                        result, ANSI_ITALIC, elapsed, ANSI_RESET
+                        result,
+                        output.italic(),
+                        elapsed,
+                        output.reset()
                     );
+                    Some((result.to_string(), elapsed))
                 }
                 None => {
-                    println!("not solved.")
+                    println!("not solved.");
+                    None
                 }
             }
         }
 
-        println!("🎄 {}Part {}{} 🎄", ANSI_BOLD, $part, ANSI_RESET);
-        print_result($solver, $input);
+        let output = advent_of_code::config::get().output;
+        let checking = advent_of_code::shrink::checking();
+        let result = match checking {
+            Some(part) => {
+                if part == $part {
+                    advent_of_code::shrink::report($solver($input));
+                }
+                None
+            }
+            None => {
+                println!("{}", output.banner(&format!("Part {}", $part)));
+                print_result($solver, $input, output)
+            }
+        };
+
+        // solutions are binaries named after their day, e.g. `01`. benches include them as modules.
+        // only normal solves are recorded: `--shrink-check` has no result here, and races and
+        // `--fuzz-diff` never get here.
+        let day = option_env!("CARGO_BIN_NAME").and_then(|name| name.parse::<u8>().ok());
+        if let (Some(day), Some((result, elapsed))) = (day, result) {
+            history::record(day, $part, &result, elapsed);
+            match Answers::load(day).get($part) {
+                Some(expected) if result != expected => {
+                    println!("⚠️ does not match the known answer {}.", expected);
+                }
+                _ => {}
+            }
+        }
     }};
 }
 
+/// how `read_file` cleans up a file before handing it to a solution.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Normalize {
+    /// convert CRLF line endings to LF.
+    pub crlf: bool,
+    /// strip a leading UTF-8 byte order mark.
+    pub bom: bool,
+    /// remove all trailing line breaks.
+    pub trim_trailing_newlines: bool,
+}
+
+impl Default for Normalize {
+    fn default() -> Self {
+        Normalize {
+            crlf: true,
+            bom: true,
+            trim_trailing_newlines: false,
+        }
+    }
+}
+
+impl Normalize {
+    /// leaves the text exactly as it is on disk.
+    pub fn none() -> Self {
+        Normalize {
+            crlf: false,
+            bom: false,
+            trim_trailing_newlines: false,
+        }
+    }
+
+    pub fn trim_trailing_newlines(self) -> Self {
+        Normalize {
+            trim_trailing_newlines: true,
+            ..self
+        }
+    }
+
+    pub fn apply(&self, text: &str) -> String {
+        let text = if self.bom {
+            text.strip_prefix('\u{feff}').unwrap_or(text)
+        } else {
+            text
+        };
+        let mut text = if self.crlf {
+            text.replace("\r\n", "\n")
+        } else {
+            text.to_string()
+        };
+        if self.trim_trailing_newlines {
+            let len = text.trim_end_matches(['\n', '\r']).len();
+            text.truncate(len);
+        }
+        text
+    }
+}
+
+/// reads `{day}.txt` from `folder` (`inputs`, `examples` or `answers`), resolved through
+/// `paths` in the config and decrypted if needed, with line endings normalised to LF and any BOM
+/// removed.
 pub fn read_file(folder: &str, day: u8) -> String {
-    let cwd = env::current_dir().unwrap();
+    read_file_with(folder, day, Normalize::default())
+}
+
+/// like `read_file`, cleaning up the file as `normalize` says.
+pub fn read_file_with(folder: &str, day: u8, normalize: Normalize) -> String {
+    let bytes = read_file_raw(folder, day);
+    let text = String::from_utf8(bytes).expect("input file is not valid UTF-8");
+    normalize.apply(&text)
+}
+
+/// reads `NN-regression-K.txt` from the examples folder, as written by `cargo shrink`.
+pub fn read_regression(day: u8, number: u32) -> String {
+    let filepath = config::get()
+        .paths
+        .examples
+        .join(format!("{:02}-regression-{}.txt", day, number));
+    std::fs::read_to_string(&filepath).unwrap_or_else(|e| {
+        panic!(
+            "could not open regression file {}: {}",
+            filepath.display(),
+            e
+        )
+    })
+}
 
-    let filepath = cwd.join("src").join(folder).join(format!("{:02}.txt", day));
+/// reads a file byte for byte, for days that need the input exactly as it was downloaded.
+pub fn read_file_raw(folder: &str, day: u8) -> Vec<u8> {
+    let config = config::get();
+    let filepath = config.paths.folder(folder).join(format!("{:02}.txt", day));
 
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    let f = crypt::read(&filepath, &config.encryption);
+    f.unwrap_or_else(|e| panic!("could not open input file: {}", e))
 }
 
 fn parse_time(val: &str, postfix: &str) -> f64 {
@@ -77,6 +232,26 @@ pub fn parse_exec_time(output: &str) -> f64 {
     })
 }
 
+/// compares the implementations of each part on generated inputs when the day is run with
+/// `--fuzz-diff`, e.g. by `cargo fuzz-diff 08`, and returns from `main` afterwards.
+#[macro_export]
+macro_rules! fuzz_diff {
+    ($generate:path, $($part:literal => [$($solver:path),+ $(,)?]),+ $(,)?) => {
+        if let Some(settings) = advent_of_code::fuzz::settings() {
+            let mut agree = true;
+            $(
+                let implementations: &[advent_of_code::race::Implementation<_>] =
+                    &[$((stringify!($solver), $solver)),+];
+                agree &= advent_of_code::fuzz::run_part($part, $generate, implementations, &settings);
+            )+
+            if !agree {
+                std::process::exit(1);
+            }
+            return;
+        }
+    };
+}
+
 /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
 #[cfg(test)]
 macro_rules! assert_approx_eq {
@@ -122,15 +297,39 @@ mod tests {
             10400.50_f64
         );
     }
+
+    #[test]
+    fn test_normalize() {
+        let text = "\u{feff}1000\r\n2000\r\n\r\n3000\r\n\r\n";
+
+        assert_eq!(Normalize::default().apply(text), "1000\n2000\n\n3000\n\n");
+        assert_eq!(
+            Normalize::default().trim_trailing_newlines().apply(text),
+            "1000\n2000\n\n3000"
+        );
+        assert_eq!(Normalize::none().apply(text), text);
+        assert_eq!(
+            Normalize::none().trim_trailing_newlines().apply(text),
+            "\u{feff}1000\r\n2000\r\n\r\n3000"
+        );
+        assert_eq!(Normalize::default().apply("a\rb\n"), "a\rb\n");
+    }
 }
 
 pub mod aoc_cli {
     use std::{
         fmt::Display,
-        fs::create_dir_all,
+        fs::{self, create_dir_all},
+        path::Path,
         process::{Command, Output, Stdio},
     };
 
+    use crate::{
+        clock::{self, Clock, SystemClock},
+        config, crypt,
+        manifest::{Entry, Manifest},
+    };
+
     pub enum AocCliError {
         CommandNotFound,
         CommandNotCallable,
@@ -152,7 +351,7 @@ pub mod aoc_cli {
     }
 
     pub fn check() -> Result<(), AocCliError> {
-        Command::new("aoc")
+        Command::new(&config::get().aoc_executable)
             .arg("-V")
             .output()
             .map_err(|_| AocCliError::CommandNotFound)?;
@@ -165,51 +364,102 @@ pub mod aoc_cli {
         call_aoc_cli(&args)
     }
 
-    pub fn download(day: u8, year: Option<u16>) -> Result<Output, AocCliError> {
-        let input_path = get_input_path(day);
-
-        let puzzle_path = get_puzzle_path(day);
-        create_dir_all("src/puzzles").map_err(|_| AocCliError::IoError)?;
-
-        let args = build_args(
-            "download",
-            &[
-                "--overwrite".into(),
-                "--input-file".into(),
-                input_path.to_string(),
-                "--puzzle-file".into(),
-                puzzle_path.to_string(),
-            ],
-            day,
-            year,
-        );
+    #[derive(Copy, Clone, Eq, PartialEq, Debug)]
+    enum Fetch {
+        Everything,
+        PuzzleOnly,
+        Nothing,
+    }
 
-        let output = call_aoc_cli(&args)?;
+    fn is_present(path: &Path) -> bool {
+        fs::metadata(path).map(|m| m.len() > 0).unwrap_or(false)
+    }
 
-        if output.status.success() {
-            println!("---");
-            println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-            println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
-            Ok(output)
+    /// an input that is already on disk is never fetched again unless forced, so that re-running
+    /// the command neither hits the server nor replaces a hand-fixed file.
+    fn what_to_fetch(input_present: bool, puzzle_present: bool, force: bool) -> Fetch {
+        if force || !input_present {
+            Fetch::Everything
+        } else if !puzzle_present {
+            Fetch::PuzzleOnly
         } else {
-            Err(AocCliError::BadExitStatus(output))
+            Fetch::Nothing
         }
     }
 
-    fn get_input_path(day: u8) -> String {
-        let day_padded = format!("{:02}", day);
-        format!("src/inputs/{}.txt", day_padded)
-    }
+    pub fn download(day: u8, year: Option<u16>, force: bool) -> Result<(), AocCliError> {
+        let paths = &config::get().paths;
+        let input_path = paths.input(day);
+        let puzzle_path = paths.puzzle(day);
+        let manifest_path = Manifest::path(paths);
+
+        // an input that is only stored encrypted is present as well
+        let encryption = &config::get().encryption;
+        let input_present = is_present(&input_path)
+            || encryption.enabled && is_present(&crypt::encrypted_path(&input_path));
+        let fetch = what_to_fetch(input_present, is_present(&puzzle_path), force);
+        if fetch == Fetch::Nothing {
+            let manifest = Manifest::load(&manifest_path).map_err(|_| AocCliError::IoError)?;
+            match manifest.get(day) {
+                Some(entry) => println!(
+                    "🎄 Input for day {} was downloaded at {}, skipping.",
+                    day,
+                    clock::format_timestamp(entry.fetched_at)
+                ),
+                None => println!("🎄 Input for day {} is already present, skipping.", day),
+            }
+            println!("Use --force to download it again.");
+            return Ok(());
+        }
+
+        create_dir_all(&paths.puzzles).map_err(|_| AocCliError::IoError)?;
+        create_dir_all(&paths.inputs).map_err(|_| AocCliError::IoError)?;
+
+        let mut args = vec![
+            "--input-file".into(),
+            input_path.display().to_string(),
+            "--puzzle-file".into(),
+            puzzle_path.display().to_string(),
+        ];
+        // only a full download may replace files, so a puzzle-only fetch cannot touch existing ones
+        match fetch {
+            Fetch::Everything => args.push("--overwrite".into()),
+            Fetch::PuzzleOnly => args.push("--puzzle-only".into()),
+            Fetch::Nothing => unreachable!(),
+        }
+        let year = year.or(config::get().year);
+        let args = build_args("download", &args, day, year);
+
+        let output = call_aoc_cli(&args)?;
 
-    fn get_puzzle_path(day: u8) -> String {
-        let day_padded = format!("{:02}", day);
-        format!("src/puzzles/{}.md", day_padded)
+        if !output.status.success() {
+            return Err(AocCliError::BadExitStatus(output));
+        }
+
+        println!("---");
+        if fetch == Fetch::Everything {
+            let contents = fs::read(&input_path).map_err(|_| AocCliError::IoError)?;
+            let mut manifest = Manifest::load(&manifest_path).map_err(|_| AocCliError::IoError)?;
+            manifest.insert(Entry::new(day, year, &contents, SystemClock.now()));
+            manifest
+                .save(&manifest_path)
+                .map_err(|_| AocCliError::IoError)?;
+            println!(
+                "🎄 Successfully wrote input to \"{}\".",
+                input_path.display()
+            );
+        }
+        println!(
+            "🎄 Successfully wrote puzzle to \"{}\".",
+            puzzle_path.display()
+        );
+        Ok(())
     }
 
     fn build_args(command: &str, args: &[String], day: u8, year: Option<u16>) -> Vec<String> {
         let mut cmd_args = args.to_vec();
 
-        if let Some(year) = year {
+        if let Some(year) = year.or(config::get().year) {
             cmd_args.push("--year".into());
             cmd_args.push(year.to_string());
         }
@@ -220,15 +470,31 @@ pub mod aoc_cli {
     }
 
     fn call_aoc_cli(args: &[String]) -> Result<Output, AocCliError> {
+        let executable = &config::get().aoc_executable;
+
         if cfg!(debug_assertions) {
-            println!("Calling >aoc with: {}", args.join(" "));
+            println!("Calling >{} with: {}", executable, args.join(" "));
         }
 
-        Command::new("aoc")
+        Command::new(executable)
             .args(args)
             .stdout(Stdio::inherit())
             .stderr(Stdio::inherit())
             .output()
             .map_err(|_| AocCliError::CommandNotCallable)
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn fetch_only_what_is_missing() {
+            assert_eq!(what_to_fetch(false, false, false), Fetch::Everything);
+            assert_eq!(what_to_fetch(false, true, false), Fetch::Everything);
+            assert_eq!(what_to_fetch(true, false, false), Fetch::PuzzleOnly);
+            assert_eq!(what_to_fetch(true, true, false), Fetch::Nothing);
+            assert_eq!(what_to_fetch(true, true, true), Fetch::Everything);
+        }
+    }
 }
@@ -2,23 +2,46 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
-use advent_of_code::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
-use std::process::Command;
+use advent_of_code::config;
+use std::process::{self, Command};
 
 fn main() {
+    let mut args = pico_args::Arguments::from_env();
+    let overrides = match config::Overrides::from_args(&mut args) {
+        Ok(overrides) => overrides,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let config = match config::init(&overrides) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Failed to load {}: {}", config::CONFIG_FILE, e);
+            process::exit(1);
+        }
+    };
+    let output = config.output;
+    let forwarded_args = overrides.to_args();
+
     let total: f64 = (1..=25)
         .map(|day| {
             let day = format!("{:02}", day);
 
             let mut args = vec!["run", "--bin", &day];
-            if cfg!(not(debug_assertions)) {
+            if config.release {
                 args.push("--release");
             }
+            if !forwarded_args.is_empty() {
+                args.push("--");
+                args.extend(forwarded_args.iter().map(String::as_str));
+            }
 
             let cmd = Command::new("cargo").args(&args).output().unwrap();
 
             println!("----------");
-            println!("{}| Day {} |{}", ANSI_BOLD, day, ANSI_RESET);
+            println!("{}| Day {} |{}", output.bold(), day, output.reset());
             println!("----------");
 
             let output = String::from_utf8(cmd.stdout).unwrap();
@@ -43,6 +66,10 @@ fn main() {
 
     println!(
         "{}Total:{} {}{:.2}ms{}",
-        ANSI_BOLD, ANSI_RESET, ANSI_ITALIC, total, ANSI_RESET
+        output.bold(),
+        output.reset(),
+        output.italic(),
+        total,
+        output.reset()
     );
 }
@@ -0,0 +1,339 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::{
+    collections::BTreeSet,
+    fmt::{Display, Write},
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use sha2::{Digest, Sha256};
+
+use crate::{
+    config::{Encryption, Paths},
+    crypt::{self, CryptError},
+};
+
+pub const MANIFEST_FILE: &str = "manifest.tsv";
+
+const HEADER: &str = "# day\tyear\tsize\tsha256\tfetched_at";
+
+/// a downloaded input as it was when it was fetched.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Entry {
+    pub day: u8,
+    pub year: Option<u16>,
+    pub size: u64,
+    pub sha256: String,
+    /// unix timestamp of the download.
+    pub fetched_at: u64,
+}
+
+impl Entry {
+    pub fn new(day: u8, year: Option<u16>, contents: &[u8], fetched_at: u64) -> Self {
+        Entry {
+            day,
+            year,
+            size: contents.len() as u64,
+            sha256: sha256_hex(contents),
+            fetched_at,
+        }
+    }
+
+    fn matches(&self, contents: &[u8]) -> bool {
+        self.size == contents.len() as u64 && self.sha256 == sha256_hex(contents)
+    }
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum ManifestError {
+    Io(String),
+    BadLine(usize),
+    /// an encrypted input could not be read.
+    Crypt(CryptError),
+}
+
+impl Display for ManifestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ManifestError::Io(e) => write!(f, "could not access manifest: {}", e),
+            ManifestError::BadLine(line) => write!(f, "malformed manifest entry on line {}", line),
+            ManifestError::Crypt(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+/// record of every input fetched by `cargo download`, stored next to the inputs.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct Manifest(Vec<Entry>);
+
+impl Manifest {
+    pub fn path(paths: &Paths) -> PathBuf {
+        paths.inputs.join(MANIFEST_FILE)
+    }
+
+    /// loads the manifest, treating a missing file as empty.
+    pub fn load(path: &Path) -> Result<Self, ManifestError> {
+        match fs::read_to_string(path) {
+            Ok(text) => Manifest::parse(&text),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Manifest::default()),
+            Err(e) => Err(ManifestError::Io(e.to_string())),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), ManifestError> {
+        fs::write(path, self.to_string()).map_err(|e| ManifestError::Io(e.to_string()))
+    }
+
+    pub fn parse(text: &str) -> Result<Self, ManifestError> {
+        text.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+            .map(|(line_num, line)| {
+                let bad_line = || ManifestError::BadLine(line_num + 1);
+                let fields = line.split('\t').collect::<Vec<_>>();
+                if let [day, year, size, sha256, fetched_at] = fields[..] {
+                    Ok(Entry {
+                        day: day.parse().map_err(|_| bad_line())?,
+                        year: match year {
+                            "-" => None,
+                            year => Some(year.parse().map_err(|_| bad_line())?),
+                        },
+                        size: size.parse().map_err(|_| bad_line())?,
+                        sha256: sha256.to_string(),
+                        fetched_at: fetched_at.parse().map_err(|_| bad_line())?,
+                    })
+                } else {
+                    Err(bad_line())
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(Manifest)
+    }
+
+    pub fn get(&self, day: u8) -> Option<&Entry> {
+        self.0.iter().find(|entry| entry.day == day)
+    }
+
+    /// adds `entry`, replacing any previous entry for the same day.
+    pub fn insert(&mut self, entry: Entry) {
+        self.0.retain(|e| e.day != entry.day);
+        self.0.push(entry);
+        self.0.sort_by_key(|e| e.day);
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.0
+    }
+}
+
+impl Display for Manifest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}", HEADER)?;
+        for entry in &self.0 {
+            writeln!(
+                f,
+                "{:02}\t{}\t{}\t{}\t{}",
+                entry.day,
+                entry.year.map_or("-".into(), |y| y.to_string()),
+                entry.size,
+                entry.sha256,
+                entry.fetched_at
+            )?;
+        }
+        Ok(())
+    }
+}
+
+pub fn sha256_hex(contents: &[u8]) -> String {
+    Sha256::digest(contents)
+        .iter()
+        .fold(String::with_capacity(64), |mut out, byte| {
+            write!(out, "{:02x}", byte).unwrap();
+            out
+        })
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Status {
+    Unchanged,
+    /// the file differs from what was downloaded; holds its current size.
+    Modified(u64),
+    Missing,
+    /// an input that was not fetched by `cargo download`.
+    Untracked,
+}
+
+/// compares every input on disk with the manifest. with encryption enabled, an input that is
+/// only stored encrypted is decrypted and compared.
+pub fn verify(
+    manifest: &Manifest,
+    inputs: &Path,
+    encryption: &Encryption,
+) -> Result<Vec<(u8, Status)>, ManifestError> {
+    let mut results = manifest
+        .entries()
+        .iter()
+        .map(|entry| {
+            let path = inputs.join(format!("{:02}.txt", entry.day));
+            let encrypted = encryption.enabled && crypt::encrypted_path(&path).exists();
+            if !encrypted && !path.exists() {
+                return Ok((entry.day, Status::Missing));
+            }
+            let contents = crypt::read(&path, encryption).map_err(ManifestError::Crypt)?;
+            let status = if entry.matches(&contents) {
+                Status::Unchanged
+            } else {
+                Status::Modified(contents.len() as u64)
+            };
+            Ok((entry.day, status))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let dir = match fs::read_dir(inputs) {
+        Ok(dir) => dir,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(results),
+        Err(e) => return Err(ManifestError::Io(e.to_string())),
+    };
+    let encrypted_suffix = format!(".txt.{}", crypt::EXTENSION);
+    let mut untracked = BTreeSet::new();
+    for file in dir {
+        let file = file.map_err(|e| ManifestError::Io(e.to_string()))?;
+        let day = file
+            .file_name()
+            .to_str()
+            .and_then(|name| {
+                name.strip_suffix(".txt").or_else(|| {
+                    name.strip_suffix(encrypted_suffix.as_str())
+                        .filter(|_| encryption.enabled)
+                })
+            })
+            .and_then(|day| day.parse::<u8>().ok());
+        if let Some(day) = day {
+            let is_empty = file.metadata().map(|m| m.len() == 0).unwrap_or(true);
+            if manifest.get(day).is_none() && !is_empty {
+                untracked.insert(day);
+            }
+        }
+    }
+    results.extend(untracked.into_iter().map(|day| (day, Status::Untracked)));
+
+    results.sort_by_key(|(day, _)| *day);
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypt::Key;
+
+    #[test]
+    fn sha256() {
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut manifest = Manifest::default();
+        manifest.insert(Entry::new(7, Some(2022), b"$ cd /\n", 1670389200));
+        manifest.insert(Entry::new(1, None, b"1000\n", 1669870800));
+        manifest.insert(Entry::new(7, Some(2022), b"$ ls\n", 1670389300));
+
+        let text = manifest.to_string();
+        assert!(text.starts_with(HEADER));
+        assert_eq!(Manifest::parse(&text), Ok(manifest.clone()));
+        assert_eq!(
+            manifest.entries().iter().map(|e| e.day).collect::<Vec<_>>(),
+            vec![1, 7]
+        );
+        assert_eq!(manifest.get(7).map(|e| e.fetched_at), Some(1670389300));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Manifest::parse("# header\n01\t2022\t5\n"),
+            Err(ManifestError::BadLine(2))
+        );
+        assert_eq!(
+            Manifest::parse("01\t20x2\t5\tab\t0\n"),
+            Err(ManifestError::BadLine(1))
+        );
+    }
+
+    #[test]
+    fn verify_inputs() {
+        let dir = std::env::temp_dir().join(format!("aoc-manifest-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("01.txt"), "unchanged").unwrap();
+        fs::write(dir.join("02.txt"), "hand-fixed").unwrap();
+        fs::write(dir.join("04.txt"), "placed by hand").unwrap();
+        fs::write(dir.join("05.txt"), "").unwrap();
+
+        let mut manifest = Manifest::default();
+        manifest.insert(Entry::new(1, Some(2022), b"unchanged", 0));
+        manifest.insert(Entry::new(2, Some(2022), b"downloaded", 0));
+        manifest.insert(Entry::new(3, Some(2022), b"deleted", 0));
+
+        let results = verify(&manifest, &dir, &Encryption::default());
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            results,
+            Ok(vec![
+                (1, Status::Unchanged),
+                (2, Status::Modified(10)),
+                (3, Status::Missing),
+                (4, Status::Untracked),
+            ])
+        );
+    }
+
+    #[test]
+    fn verify_encrypted_inputs() {
+        let dir = std::env::temp_dir().join(format!("aoc-manifest-enc-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let key_env = format!("AOC_TEST_MANIFEST_KEY_{}", std::process::id());
+        std::env::set_var(&key_env, "hunter2");
+        let key = Key::from_passphrase("hunter2");
+        fs::write(dir.join("01.txt.enc"), key.encrypt(b"unchanged")).unwrap();
+        fs::write(dir.join("02.txt.enc"), key.encrypt(b"hand-fixed")).unwrap();
+        fs::write(dir.join("04.txt.enc"), key.encrypt(b"placed by hand")).unwrap();
+
+        let mut manifest = Manifest::default();
+        manifest.insert(Entry::new(1, Some(2022), b"unchanged", 0));
+        manifest.insert(Entry::new(2, Some(2022), b"downloaded", 0));
+        manifest.insert(Entry::new(3, Some(2022), b"deleted", 0));
+
+        let enabled = Encryption {
+            enabled: true,
+            key_env,
+        };
+        let results = verify(&manifest, &dir, &enabled);
+        let disabled = verify(&manifest, &dir, &Encryption::default());
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            results,
+            Ok(vec![
+                (1, Status::Unchanged),
+                (2, Status::Modified(10)),
+                (3, Status::Missing),
+                (4, Status::Untracked),
+            ])
+        );
+        assert_eq!(
+            disabled,
+            Ok(vec![
+                (1, Status::Missing),
+                (2, Status::Missing),
+                (3, Status::Missing),
+            ])
+        );
+    }
+}
@@ -0,0 +1,244 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::{answers::Answers, scaffold::Options};
+
+/// one part of a puzzle description written by `aoc download`, split into prose and fenced
+/// code blocks.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+struct Part {
+    prose: Vec<String>,
+    /// code blocks, each with the prose line that came right before it.
+    code_blocks: Vec<(String, String)>,
+}
+
+fn split_parts(markdown: &str) -> (Part, Option<Part>) {
+    let mut parts = vec![Part::default()];
+    let mut block: Option<Vec<&str>> = None;
+    let mut last_prose = String::new();
+
+    for line in markdown.lines() {
+        let part = parts.last_mut().unwrap();
+        match &mut block {
+            Some(lines) if line.trim_start().starts_with("```") => {
+                let mut code = lines.join("\n");
+                code.push('\n');
+                part.code_blocks.push((last_prose.clone(), code));
+                block = None;
+            }
+            Some(lines) => lines.push(line),
+            None if line.trim_start().starts_with("```") => block = Some(Vec::new()),
+            None if line.contains("Part Two") && line.contains("---") => {
+                parts.push(Part::default())
+            }
+            None if !line.trim().is_empty() => {
+                last_prose = line.to_string();
+                part.prose.push(line.to_string());
+            }
+            None => {}
+        }
+    }
+
+    let part_two = if parts.len() > 1 { parts.pop() } else { None };
+    (parts.remove(0), part_two)
+}
+
+/// the example input of a puzzle: the first code block introduced by a line mentioning an
+/// example, or the first code block if no line does.
+pub fn example(markdown: &str) -> Option<String> {
+    let (part_one, _) = split_parts(markdown);
+    let blocks = &part_one.code_blocks;
+    blocks
+        .iter()
+        .find(|(intro, _)| intro.to_lowercase().contains("example"))
+        .or_else(|| blocks.first())
+        .map(|(_, code)| code.clone())
+}
+
+/// inline code spans in `line` that are emphasized, e.g. `` `*24000*` `` or `` *`24000`* ``.
+fn emphasized_code(line: &str) -> Vec<String> {
+    let mut spans = Vec::new();
+    let mut rest = line;
+    while let Some(start) = rest.find('`') {
+        let after = &rest[start + 1..];
+        let end = match after.find('`') {
+            Some(end) => end,
+            None => break,
+        };
+        let code = &after[..end];
+        let inner = code.trim_matches(|c| c == '*' || c == '_');
+        let emphasized_inside = inner.len() < code.len();
+        let emphasized_outside =
+            rest[..start].ends_with(['*', '_']) && after[end + 1..].starts_with(['*', '_']);
+        if (emphasized_inside || emphasized_outside) && !inner.is_empty() {
+            spans.push(inner.to_string());
+        }
+        rest = &after[end + 1..];
+    }
+    spans
+}
+
+/// the example answers of a puzzle: the last emphasized code span of each part.
+/// part two is only present in the description once part one has been solved.
+pub fn example_answers(markdown: &str) -> Answers {
+    let last_answer = |part: &Part| {
+        part.prose
+            .iter()
+            .flat_map(|line| emphasized_code(line))
+            .last()
+    };
+    let (part_one, part_two) = split_parts(markdown);
+    Answers {
+        part_one: last_answer(&part_one),
+        part_two: part_two.as_ref().and_then(last_answer),
+    }
+}
+
+/// whether `answer` is a valid value of the integer type `answer_type`, or `None` if that is not
+/// an integer type.
+fn fits_integer(answer: &str, answer_type: &str) -> Option<bool> {
+    macro_rules! fits {
+        ($($t:ty),+) => {
+            match answer_type {
+                $(stringify!($t) => Some(answer.parse::<$t>().is_ok()),)+
+                _ => None,
+            }
+        };
+    }
+    fits!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize)
+}
+
+/// rust expression for an answer, as compared with the `Option<answer_type>` returned by a part.
+/// `None` if the answer is not a value of an integer `answer_type`.
+pub fn answer_literal(answer: &str, answer_type: &str) -> Option<String> {
+    let string = || format!("Some({:?}.to_string())", answer);
+    let number = || format!("Some({})", answer);
+    match (answer_type, fits_integer(answer, answer_type)) {
+        ("String", _) => Some(string()),
+        (_, Some(true)) => Some(number()),
+        (_, Some(false)) => None,
+        // a type of the user's own, guess from the answer
+        (_, None) if fits_integer(answer, "i128") == Some(true) => Some(number()),
+        (_, None) => Some(string()),
+    }
+}
+
+/// replaces the placeholder `None` assertions of a scaffolded module with the example answers,
+/// written for the answer types in `options`. returns the new module and the parts that were
+/// filled in.
+pub fn fill_assertions(module: &str, answers: &Answers, options: &Options) -> (String, Vec<u8>) {
+    let mut module = module.to_string();
+    let mut filled = Vec::new();
+    for (part, name, answer_type) in [
+        (1, "part_one", &options.part_one_type),
+        (2, "part_two", &options.part_two_type),
+    ] {
+        let placeholder = format!("assert_eq!({}(&input), None);", name);
+        if !module.contains(&placeholder) {
+            continue;
+        }
+        if let Some(literal) = answers
+            .get(part)
+            .and_then(|answer| answer_literal(answer, answer_type))
+        {
+            let assertion = format!("assert_eq!({}(&input), {});", name, literal);
+            module = module.replacen(&placeholder, &assertion, 1);
+            filled.push(part);
+        }
+    }
+    (module, filled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str = "\
+## --- Day 1: Calorie Counting ---
+
+The Elves take turns writing down the number of Calories:
+
+For example, suppose the Elves finish writing their items' Calories:
+
+```
+1000
+2000
+
+3000
+```
+
+In the example above, this is `*24000*` (carried by the fourth Elf).
+
+Find the Elf carrying the most Calories. *How many total Calories is that Elf carrying?*
+
+## --- Part Two ---
+
+In the example above, the top three Elves are carrying `24000`, `11000` and `10000`. The sum is *`45000`* Calories.
+";
+
+    #[test]
+    fn extract_example() {
+        assert_eq!(example(PUZZLE), Some("1000\n2000\n\n3000\n".into()));
+        assert_eq!(example("```\nfirst\n```\n"), Some("first\n".into()));
+        assert_eq!(example("no code here"), None);
+    }
+
+    #[test]
+    fn extract_answers() {
+        let answers = example_answers(PUZZLE);
+        assert_eq!(answers.get(1), Some("24000"));
+        assert_eq!(answers.get(2), Some("45000"));
+
+        let (part_one, _) = PUZZLE.split_once("## --- Part Two").unwrap();
+        assert_eq!(example_answers(part_one).get(2), None);
+    }
+
+    #[test]
+    fn fill_module_assertions() {
+        let module = "assert_eq!(part_one(&input), None);\nassert_eq!(part_two(&input), None);\n";
+        let answers = Answers {
+            part_one: Some("CMZ".into()),
+            part_two: None,
+        };
+        let options = Options {
+            part_one_type: "String".into(),
+            ..Options::default()
+        };
+        let (filled, parts) = fill_assertions(module, &answers, &options);
+        assert_eq!(parts, vec![1]);
+        assert_eq!(
+            filled,
+            "assert_eq!(part_one(&input), Some(\"CMZ\".to_string()));\nassert_eq!(part_two(&input), None);\n"
+        );
+
+        // an answer that does not fit the declared type is left for the user
+        let answers = Answers {
+            part_one: Some("24000".into()),
+            part_two: Some("5000000000".into()),
+        };
+        let (filled, parts) = fill_assertions(module, &answers, &Options::default());
+        assert_eq!(parts, vec![1]);
+        assert!(filled.ends_with("Some(24000));\nassert_eq!(part_two(&input), None);\n"));
+    }
+
+    #[test]
+    fn literals() {
+        assert_eq!(
+            answer_literal("123", "String"),
+            Some("Some(\"123\".to_string())".into())
+        );
+        assert_eq!(answer_literal("-12", "i64"), Some("Some(-12)".into()));
+        assert_eq!(answer_literal("-12", "u64"), None);
+        assert_eq!(answer_literal("CMZ", "u32"), None);
+        assert_eq!(
+            answer_literal("18446744073709551615", "u64"),
+            Some("Some(18446744073709551615)".into())
+        );
+        assert_eq!(answer_literal("12", "Answer"), Some("Some(12)".into()));
+        assert_eq!(
+            answer_literal("1,2", "Answer"),
+            Some("Some(\"1,2\".to_string())".into())
+        );
+    }
+}
@@ -0,0 +1,177 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::{env, fmt::Display, process, time::Duration};
+
+use crate::{
+    bench::{self, Measurement},
+    config,
+};
+
+/// how long each implementation is measured for in a race.
+const MEASURE_FOR: Duration = Duration::from_millis(500);
+
+/// a named solution of a part, as registered with `solve!(part, input, [a, b, ...])`.
+pub type Implementation<T> = (&'static str, fn(&str) -> Option<T>);
+
+/// whether the day was started with `--race`, e.g. `cargo solve 06 -- --race`.
+pub fn enabled() -> bool {
+    env::args().any(|arg| arg == "--race")
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Outcome {
+    pub name: &'static str,
+    pub answer: Option<String>,
+    pub measurement: Measurement,
+}
+
+/// runs every implementation once, returning the names and answers of those that disagree with
+/// the first one. cheap enough to call from unit tests.
+pub fn disagreements<T: Display>(
+    implementations: &[Implementation<T>],
+    input: &str,
+) -> Vec<(&'static str, Option<String>)> {
+    let answers = implementations
+        .iter()
+        .map(|(name, func)| (*name, func(input).map(|a| a.to_string())))
+        .collect::<Vec<_>>();
+    match answers.split_first() {
+        Some(((_, expected), rest)) => rest
+            .iter()
+            .filter(|(_, answer)| answer != expected)
+            .cloned()
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+/// measures every implementation against `input`.
+pub fn race<T: Display>(implementations: &[Implementation<T>], input: &str) -> Vec<Outcome> {
+    implementations
+        .iter()
+        .map(|(name, func)| Outcome {
+            name,
+            answer: func(input).map(|a| a.to_string()),
+            measurement: bench::measure_for(|| func(input), MEASURE_FOR),
+        })
+        .collect()
+}
+
+/// a table of the results, with each mean time relative to the first implementation.
+/// answers that differ from the first one are marked with `✘`.
+pub fn render(results: &[Outcome]) -> String {
+    let name_width = results
+        .iter()
+        .map(|r| r.name.len())
+        .max()
+        .unwrap_or(0)
+        .max(14);
+    let mut out = format!(
+        "{:<name_width$}  {:<16}  {:>10}  {:>10}  relative\n",
+        "implementation", "answer", "mean", "min"
+    );
+    let (expected, baseline) = match results.first() {
+        Some(first) => (&first.answer, first.measurement.mean.as_secs_f64()),
+        None => return out,
+    };
+    for result in results {
+        let answer = match &result.answer {
+            Some(answer) => answer.clone(),
+            None => "not solved".into(),
+        };
+        let marker = if &result.answer == expected {
+            ""
+        } else {
+            " ✘"
+        };
+        let relative = result.measurement.mean.as_secs_f64() / baseline.max(f64::MIN_POSITIVE);
+        out.push_str(&format!(
+            "{:<name_width$}  {:<16}  {:>10}  {:>10}  {:.2}x\n",
+            result.name,
+            format!("{}{}", answer, marker),
+            format!("{:.2?}", result.measurement.mean),
+            format!("{:.2?}", result.measurement.min),
+            relative
+        ));
+    }
+    out
+}
+
+/// races the implementations of a part and prints the comparison. exits with an error if they
+/// do not agree.
+pub fn run<T: Display>(part: u8, implementations: &[Implementation<T>], input: &str) {
+    let output = config::get().output;
+    println!("{}", output.banner(&format!("Part {} race", part)));
+    let results = race(implementations, input);
+    print!("{}", render(&results));
+
+    if results.iter().any(|r| r.answer != results[0].answer) {
+        eprintln!(
+            "implementations of part {} disagree, see the answers marked with ✘.",
+            part
+        );
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn length(input: &str) -> Option<usize> {
+        Some(input.len())
+    }
+
+    fn count_chars(input: &str) -> Option<usize> {
+        Some(input.chars().count())
+    }
+
+    fn lines(input: &str) -> Option<usize> {
+        Some(input.lines().count())
+    }
+
+    #[test]
+    fn find_disagreements() {
+        let implementations: [Implementation<usize>; 3] =
+            [("length", length), ("chars", count_chars), ("lines", lines)];
+        assert_eq!(disagreements(&implementations, "a"), vec![]);
+        assert_eq!(
+            disagreements(&implementations, "ab\ncd"),
+            vec![("lines", Some("2".into()))]
+        );
+    }
+
+    #[test]
+    fn render_table() {
+        let measurement = |micros| Measurement {
+            runs: 10,
+            mean: Duration::from_micros(micros),
+            min: Duration::from_micros(micros),
+            max: Duration::from_micros(micros),
+        };
+        let results = [
+            Outcome {
+                name: "part_one",
+                answer: Some("7".into()),
+                measurement: measurement(20),
+            },
+            Outcome {
+                name: "part_one_bitmask",
+                answer: Some("8".into()),
+                measurement: measurement(5),
+            },
+        ];
+        let table = render(&results);
+        let lines = table.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines[1],
+            "part_one          7                    20.00µs     20.00µs  1.00x"
+        );
+        assert_eq!(
+            lines[2],
+            "part_one_bitmask  8 ✘                   5.00µs      5.00µs  0.25x"
+        );
+    }
+}
@@ -0,0 +1,641 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::{
+    fmt::Display,
+    fs::{self, create_dir_all, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::config::Paths;
+
+/// built-in module template, used unless `--template` or `templates/default.rs` says otherwise.
+/// placeholders are listed in `Vars::get`.
+pub const MODULE_TEMPLATE: &str = r###"pub fn part_one(input: &str) -> Option<{{PART_ONE_TYPE}}> {
+    None
+}
+
+pub fn part_two(input: &str) -> Option<{{PART_TWO_TYPE}}> {
+    None
+}
+
+fn main() {
+    let input = &advent_of_code::read_file("inputs", {{DAY}});
+    advent_of_code::solve!(1, part_one, input);
+    advent_of_code::solve!(2, part_two, input);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = advent_of_code::read_file("examples", {{DAY}});
+        assert_eq!(part_one(&input), None);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = advent_of_code::read_file("examples", {{DAY}});
+        assert_eq!(part_two(&input), None);
+    }
+}
+"###;
+
+pub const DEFAULT_TEMPLATE: &str = "default";
+
+/// benchmark harness for a day, run with `cargo bench --bench NN`.
+pub const BENCH_TEMPLATE: &str = r###"#[allow(dead_code, unused_imports)]
+#[path = "../src/bin/{{DAY_PADDED}}.rs"]
+mod day;
+
+fn main() {
+    let input = &advent_of_code::bench::input({{DAY}});
+    advent_of_code::bench::run("part_one", || day::part_one(input));
+    advent_of_code::bench::run("part_two", || day::part_two(input));
+}
+"###;
+
+pub const CARGO_MANIFEST: &str = "Cargo.toml";
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    ReadTemplate(PathBuf, io::Error),
+    UnknownPlaceholder(String),
+    ModuleExists(PathBuf),
+    Backup(PathBuf, io::Error),
+    Write(PathBuf, io::Error),
+    Manifest(PathBuf, io::Error),
+}
+
+impl Display for ScaffoldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScaffoldError::ReadTemplate(path, e) => {
+                write!(f, "Failed to read template \"{}\": {}", path.display(), e)
+            }
+            ScaffoldError::UnknownPlaceholder(name) => {
+                write!(f, "Template contains unknown placeholder {{{{{}}}}}", name)
+            }
+            ScaffoldError::ModuleExists(path) => write!(
+                f,
+                "Module file \"{}\" already exists. Use --force to overwrite it.",
+                path.display()
+            ),
+            ScaffoldError::Backup(path, e) => {
+                write!(f, "Failed to back up \"{}\": {}", path.display(), e)
+            }
+            ScaffoldError::Write(path, e) => {
+                write!(f, "Failed to write \"{}\": {}", path.display(), e)
+            }
+            ScaffoldError::Manifest(path, e) => {
+                write!(f, "Failed to update \"{}\": {}", path.display(), e)
+            }
+        }
+    }
+}
+
+/// what to generate for a day.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Options {
+    /// name of a template in the templates folder, e.g. `grid` for `templates/grid.rs`.
+    pub template: Option<String>,
+    pub part_one_type: String,
+    pub part_two_type: String,
+    pub year: Option<u16>,
+    /// only report what would be written.
+    pub dry_run: bool,
+    /// overwrite an existing module and bench, keeping backups.
+    pub force: bool,
+    /// also empty existing non-empty input and example files, keeping backups.
+    pub reset_data: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            template: None,
+            part_one_type: "u32".into(),
+            part_two_type: "u32".into(),
+            year: None,
+            dry_run: false,
+            force: false,
+            reset_data: false,
+        }
+    }
+}
+
+struct Vars<'a> {
+    day: u8,
+    options: &'a Options,
+}
+
+impl Vars<'_> {
+    fn get(&self, name: &str) -> Option<String> {
+        Some(match name {
+            "DAY" => self.day.to_string(),
+            "DAY_PADDED" => format!("{:02}", self.day),
+            "YEAR" => self
+                .options
+                .year
+                .map_or_else(|| "unknown".into(), |y| y.to_string()),
+            "PART_ONE_TYPE" => self.options.part_one_type.clone(),
+            "PART_TWO_TYPE" => self.options.part_two_type.clone(),
+            _ => return None,
+        })
+    }
+}
+
+/// replaces every `{{NAME}}` placeholder in `template`.
+fn render(template: &str, vars: &Vars) -> Result<String, ScaffoldError> {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after.find("}}").ok_or_else(|| {
+            ScaffoldError::UnknownPlaceholder(after.lines().next().unwrap_or("").into())
+        })?;
+        let name = after[..end].trim();
+        out.push_str(
+            &vars
+                .get(name)
+                .ok_or_else(|| ScaffoldError::UnknownPlaceholder(name.into()))?,
+        );
+        rest = &after[end + 2..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// loads the template named in `options`, falling back to `templates/default.rs` and then to the
+/// built-in template.
+fn load_template(paths: &Paths, options: &Options) -> Result<String, ScaffoldError> {
+    let name = options.template.as_deref().unwrap_or(DEFAULT_TEMPLATE);
+    let path = paths.templates.join(format!("{}.rs", name));
+    match fs::read_to_string(&path) {
+        Ok(template) => Ok(template),
+        Err(e) if e.kind() == io::ErrorKind::NotFound && options.template.is_none() => {
+            Ok(MODULE_TEMPLATE.to_string())
+        }
+        Err(e) => Err(ScaffoldError::ReadTemplate(path, e)),
+    }
+}
+
+/// renders the solution module for `day` without writing it.
+pub fn render_module(paths: &Paths, day: u8, options: &Options) -> Result<String, ScaffoldError> {
+    render(&load_template(paths, options)?, &Vars { day, options })
+}
+
+pub fn module_path(day: u8) -> PathBuf {
+    Path::new("src/bin").join(format!("{:02}.rs", day))
+}
+
+pub fn bench_path(day: u8) -> PathBuf {
+    Path::new("benches").join(format!("{:02}.rs", day))
+}
+
+pub fn render_bench(day: u8) -> String {
+    let options = Options::default();
+    render(
+        BENCH_TEMPLATE,
+        &Vars {
+            day,
+            options: &options,
+        },
+    )
+    .expect("bench template only uses known placeholders")
+}
+
+/// adds a `[[bench]]` target for `day` to the text of `Cargo.toml`. benches need their own
+/// target to opt out of the unstable built-in harness. returns `None` if it is already there.
+pub fn register_bench(manifest: &str, day: u8) -> Option<String> {
+    let name = format!("name = \"{:02}\"", day);
+    let registered = manifest
+        .split("[[bench]]")
+        .skip(1)
+        .any(|target| target.lines().any(|line| line.trim() == name));
+    if registered {
+        return None;
+    }
+
+    let mut manifest = manifest.to_string();
+    if !manifest.ends_with('\n') {
+        manifest.push('\n');
+    }
+    manifest.push_str(&format!(
+        "\n[[bench]]\n{}\npath = \"{}\"\nharness = false\n",
+        name,
+        bench_path(day).display()
+    ));
+    Some(manifest)
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum FileKind {
+    Module,
+    Input,
+    Example,
+    Bench,
+}
+
+impl Display for FileKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FileKind::Module => write!(f, "module file"),
+            FileKind::Input => write!(f, "input file"),
+            FileKind::Example => write!(f, "example file"),
+            FileKind::Bench => write!(f, "bench file"),
+        }
+    }
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Action {
+    Create,
+    /// replace the file after copying it to `backup`.
+    Overwrite {
+        backup: PathBuf,
+    },
+    /// leave an existing file untouched.
+    Keep,
+}
+
+/// one file that scaffolding creates, overwrites or keeps.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Step {
+    pub kind: FileKind,
+    pub path: PathBuf,
+    pub action: Action,
+    contents: String,
+}
+
+impl Step {
+    pub fn describe(&self, dry_run: bool) -> String {
+        let (kind, path) = (self.kind, self.path.display());
+        let empty = match kind {
+            FileKind::Input | FileKind::Example => "empty ",
+            FileKind::Module | FileKind::Bench => "",
+        };
+        match (&self.action, dry_run) {
+            (Action::Create, false) => format!("Created {}{} \"{}\"", empty, kind, path),
+            (Action::Create, true) => format!("Would create {}{} \"{}\"", empty, kind, path),
+            (Action::Overwrite { backup }, false) => format!(
+                "Overwrote {} \"{}\" (backup: \"{}\")",
+                kind,
+                path,
+                backup.display()
+            ),
+            (Action::Overwrite { backup }, true) => format!(
+                "Would overwrite {} \"{}\" (backup: \"{}\")",
+                kind,
+                path,
+                backup.display()
+            ),
+            (Action::Keep, false) => format!("Kept existing {} \"{}\"", kind, path),
+            (Action::Keep, true) => format!("Would keep existing {} \"{}\"", kind, path),
+        }
+    }
+}
+
+/// first unused `<path>.bak`, `<path>.bak.2`, ... name.
+fn backup_path(path: &Path) -> PathBuf {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let mut backup = path.with_file_name(format!("{}.bak", name));
+    let mut n = 2;
+    while backup.exists() {
+        backup = path.with_file_name(format!("{}.bak.{}", name, n));
+        n += 1;
+    }
+    backup
+}
+
+/// decides what happens to each file. an existing module is only replaced with `force`, existing
+/// non-empty inputs and examples only with `reset_data`.
+pub fn plan(
+    module_path: &Path,
+    input_path: &Path,
+    example_path: &Path,
+    module: String,
+    options: &Options,
+) -> Result<Vec<Step>, ScaffoldError> {
+    let module_action = if !module_path.exists() {
+        Action::Create
+    } else if options.force {
+        Action::Overwrite {
+            backup: backup_path(module_path),
+        }
+    } else {
+        return Err(ScaffoldError::ModuleExists(module_path.to_path_buf()));
+    };
+
+    let data_action = |path: &Path| match fs::metadata(path) {
+        Err(_) => Action::Create,
+        Ok(metadata) if metadata.len() > 0 && options.reset_data => Action::Overwrite {
+            backup: backup_path(path),
+        },
+        Ok(_) => Action::Keep,
+    };
+
+    Ok(vec![
+        Step {
+            kind: FileKind::Module,
+            path: module_path.to_path_buf(),
+            action: module_action,
+            contents: module,
+        },
+        Step {
+            kind: FileKind::Input,
+            path: input_path.to_path_buf(),
+            action: data_action(input_path),
+            contents: String::new(),
+        },
+        Step {
+            kind: FileKind::Example,
+            path: example_path.to_path_buf(),
+            action: data_action(example_path),
+            contents: String::new(),
+        },
+    ])
+}
+
+/// an existing bench is kept unless `force` is set.
+pub fn plan_bench(bench_path: &Path, bench: String, force: bool) -> Step {
+    let action = if !bench_path.exists() {
+        Action::Create
+    } else if force {
+        Action::Overwrite {
+            backup: backup_path(bench_path),
+        }
+    } else {
+        Action::Keep
+    };
+    Step {
+        kind: FileKind::Bench,
+        path: bench_path.to_path_buf(),
+        action,
+        contents: bench,
+    }
+}
+
+fn write_file(path: &Path, contents: &str, create_new: bool) -> Result<(), io::Error> {
+    if let Some(parent) = path.parent() {
+        create_dir_all(parent)?;
+    }
+    let mut file = if create_new {
+        OpenOptions::new().write(true).create_new(true).open(path)?
+    } else {
+        File::create(path)?
+    };
+    file.write_all(contents.as_bytes())
+}
+
+pub fn execute(step: &Step) -> Result<(), ScaffoldError> {
+    let write_error = |e| ScaffoldError::Write(step.path.clone(), e);
+    match &step.action {
+        Action::Create => write_file(&step.path, &step.contents, true).map_err(write_error),
+        Action::Overwrite { backup } => {
+            fs::copy(&step.path, backup)
+                .map_err(|e| ScaffoldError::Backup(step.path.clone(), e))?;
+            write_file(&step.path, &step.contents, false).map_err(write_error)
+        }
+        Action::Keep => Ok(()),
+    }
+}
+
+/// makes sure `Cargo.toml` has a bench target for `day`, printing what was done.
+pub fn ensure_bench_registered(day: u8, dry_run: bool) -> Result<(), ScaffoldError> {
+    let path = Path::new(CARGO_MANIFEST);
+    let error = |e| ScaffoldError::Manifest(path.to_path_buf(), e);
+    let manifest = fs::read_to_string(path).map_err(error)?;
+    if let Some(manifest) = register_bench(&manifest, day) {
+        if dry_run {
+            println!(
+                "Would register bench \"{:02}\" in \"{}\"",
+                day,
+                path.display()
+            );
+        } else {
+            fs::write(path, manifest).map_err(error)?;
+            println!("Registered bench \"{:02}\" in \"{}\"", day, path.display());
+        }
+    }
+    Ok(())
+}
+
+/// creates the solution module, input file, example file and bench for `day`.
+pub fn scaffold(paths: &Paths, day: u8, options: &Options) -> Result<(), ScaffoldError> {
+    let module = render_module(paths, day, options)?;
+    let mut steps = plan(
+        &module_path(day),
+        &paths.input(day),
+        &paths.example(day),
+        module,
+        options,
+    )?;
+    steps.push(plan_bench(
+        &bench_path(day),
+        render_bench(day),
+        options.force,
+    ));
+
+    for step in &steps {
+        if !options.dry_run {
+            execute(step)?;
+        }
+        println!("{}", step.describe(options.dry_run));
+    }
+
+    ensure_bench_registered(day, options.dry_run)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_placeholders() {
+        let options = Options {
+            part_one_type: "u64".into(),
+            part_two_type: "String".into(),
+            year: Some(2022),
+            ..Options::default()
+        };
+        let vars = Vars {
+            day: 7,
+            options: &options,
+        };
+
+        assert_eq!(
+            render(
+                "{{DAY}} {{ DAY_PADDED }} {{YEAR}} {{PART_ONE_TYPE}} {{PART_TWO_TYPE}}",
+                &vars
+            )
+            .unwrap(),
+            "7 07 2022 u64 String"
+        );
+        assert!(matches!(
+            render("{{DYA}}", &vars),
+            Err(ScaffoldError::UnknownPlaceholder(name)) if name == "DYA"
+        ));
+        assert!(matches!(
+            render("{{DAY", &vars),
+            Err(ScaffoldError::UnknownPlaceholder(_))
+        ));
+    }
+
+    #[test]
+    fn builtin_template() {
+        let options = Options {
+            part_one_type: "String".into(),
+            ..Options::default()
+        };
+        let module = render(
+            MODULE_TEMPLATE,
+            &Vars {
+                day: 5,
+                options: &options,
+            },
+        )
+        .unwrap();
+
+        assert!(module.contains("pub fn part_one(input: &str) -> Option<String> {"));
+        assert!(module.contains("pub fn part_two(input: &str) -> Option<u32> {"));
+        assert!(module.contains("advent_of_code::read_file(\"inputs\", 5);"));
+        assert!(!module.contains("{{"));
+    }
+
+    #[test]
+    fn repository_templates() {
+        let paths = Paths::default();
+        for template in ["grid", "parse"] {
+            let options = Options {
+                template: Some(template.into()),
+                ..Options::default()
+            };
+            let module = render_module(&paths, 12, &options).unwrap();
+            assert!(
+                module.contains("advent_of_code::read_file(\"inputs\", 12);"),
+                "{}",
+                template
+            );
+        }
+    }
+
+    #[test]
+    fn plan_and_execute() {
+        let dir = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let module = dir.join("01.rs");
+        let input = dir.join("inputs/01.txt");
+        let example = dir.join("examples/01.txt");
+        let actions = |steps: &[Step]| {
+            steps
+                .iter()
+                .map(|step| step.action.clone())
+                .collect::<Vec<_>>()
+        };
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        let options = Options::default();
+        let fresh = plan(&module, &input, &example, "v1".into(), &options).unwrap();
+        assert_eq!(
+            actions(&fresh),
+            vec![Action::Create, Action::Create, Action::Create]
+        );
+        fresh.iter().try_for_each(execute).unwrap();
+        assert_eq!(fs::read_to_string(&module).unwrap(), "v1");
+
+        // an existing module is an error without --force.
+        assert!(matches!(
+            plan(&module, &input, &example, "v2".into(), &options),
+            Err(ScaffoldError::ModuleExists(_))
+        ));
+
+        // --force replaces the module, but leaves the data files alone.
+        fs::write(&input, "puzzle input").unwrap();
+        let force = Options {
+            force: true,
+            ..Options::default()
+        };
+        let forced = plan(&module, &input, &example, "v2".into(), &force).unwrap();
+        assert_eq!(
+            actions(&forced),
+            vec![
+                Action::Overwrite {
+                    backup: dir.join("01.rs.bak")
+                },
+                Action::Keep,
+                Action::Keep
+            ]
+        );
+        forced.iter().try_for_each(execute).unwrap();
+        assert_eq!(fs::read_to_string(&module).unwrap(), "v2");
+        assert_eq!(fs::read_to_string(dir.join("01.rs.bak")).unwrap(), "v1");
+        assert_eq!(fs::read_to_string(&input).unwrap(), "puzzle input");
+
+        // --reset-data empties non-empty data files, and earlier backups are kept.
+        let reset = Options {
+            force: true,
+            reset_data: true,
+            ..Options::default()
+        };
+        let resetting = plan(&module, &input, &example, "v3".into(), &reset).unwrap();
+        assert_eq!(
+            actions(&resetting),
+            vec![
+                Action::Overwrite {
+                    backup: dir.join("01.rs.bak.2")
+                },
+                Action::Overwrite {
+                    backup: dir.join("inputs/01.txt.bak")
+                },
+                Action::Keep
+            ]
+        );
+        resetting.iter().try_for_each(execute).unwrap();
+        let input_after = fs::read_to_string(&input).unwrap();
+        let input_backup = fs::read_to_string(dir.join("inputs/01.txt.bak")).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(input_after, "");
+        assert_eq!(input_backup, "puzzle input");
+    }
+
+    #[test]
+    fn describe_steps() {
+        let step = Step {
+            kind: FileKind::Input,
+            path: "src/inputs/01.txt".into(),
+            action: Action::Create,
+            contents: String::new(),
+        };
+        assert_eq!(
+            step.describe(false),
+            "Created empty input file \"src/inputs/01.txt\""
+        );
+        assert_eq!(
+            step.describe(true),
+            "Would create empty input file \"src/inputs/01.txt\""
+        );
+    }
+
+    #[test]
+    fn bench_harness() {
+        let bench = render_bench(9);
+        assert!(bench.contains("#[path = \"../src/bin/09.rs\"]"));
+        assert!(bench.contains("advent_of_code::bench::input(9)"));
+
+        let manifest = "[package]\nname = \"advent_of_code\"\n";
+        let registered = register_bench(manifest, 9).unwrap();
+        assert_eq!(
+            registered,
+            "[package]\nname = \"advent_of_code\"\n\n[[bench]]\nname = \"09\"\npath = \"benches/09.rs\"\nharness = false\n"
+        );
+        assert_eq!(register_bench(&registered, 9), None);
+        assert!(register_bench(&registered, 10).is_some());
+    }
+}
@@ -0,0 +1,347 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::{
+    env,
+    fmt::Display,
+    path::{Path, PathBuf},
+    process,
+};
+
+use crate::{
+    json,
+    race::{self, Implementation},
+};
+
+/// exit code of a day whose implementations of the checked part disagree.
+pub const DISAGREEMENT_EXIT_CODE: i32 = 3;
+/// prefix of the line a day prints to stderr before exiting with `DISAGREEMENT_EXIT_CODE`.
+pub const IMPLEMENTATIONS_PREFIX: &str = "implementations: ";
+/// prefix of the line with the answer a day prints to stdout while checking.
+pub const ANSWER_PREFIX: &str = "answer: ";
+
+/// the part to check if the day was started with `--shrink-check <part>` by `cargo shrink`.
+/// in this mode `solve!` only runs that part, prints only its answer and records nothing.
+pub fn checking() -> Option<u8> {
+    let args = env::args().collect::<Vec<_>>();
+    let index = args.iter().position(|arg| arg == "--shrink-check")?;
+    args.get(index + 1)?.parse().ok()
+}
+
+/// prints the answer of the checked part, so that `cargo shrink` can compare it with the known
+/// answer. prints nothing if the part is not solved.
+pub fn report<T: Display>(answer: Option<T>) {
+    if let Some(answer) = answer {
+        println!("{}{}", ANSWER_PREFIX, answer);
+    }
+}
+
+/// the answer a day reported with `report`.
+pub fn reported_answer(stdout: &str) -> Option<String> {
+    stdout
+        .lines()
+        .find_map(|line| line.strip_prefix(ANSWER_PREFIX))
+        .map(String::from)
+}
+
+/// runs every implementation of the checked part and exits if they disagree, otherwise reports
+/// the answer. a panic is left to unwind so that `cargo shrink` can see its message.
+pub fn check<T: Display>(implementations: &[Implementation<T>], input: &str) {
+    if !race::disagreements(implementations, input).is_empty() {
+        let names = implementations
+            .iter()
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>();
+        eprintln!("{}{}", IMPLEMENTATIONS_PREFIX, names.join(", "));
+        process::exit(DISAGREEMENT_EXIT_CODE);
+    }
+    let (_, solver) = implementations[0];
+    report(solver(input));
+}
+
+/// how a day failed on an input.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Failure {
+    /// where the day panicked, e.g. `panicked at src/bin/08.rs:10:5`.
+    Panic(String),
+    /// the names of the implementations that were compared.
+    Disagreement(Vec<String>),
+    /// the answer differs from the one in the known answers.
+    WrongAnswer {
+        expected: String,
+        actual: Option<String>,
+    },
+    Timeout,
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Panic(message) => write!(f, "{}", message),
+            Failure::Disagreement(names) => {
+                write!(f, "implementations disagree: {}", names.join(", "))
+            }
+            Failure::WrongAnswer { expected, actual } => write!(
+                f,
+                "answered {}, the known answer is {}",
+                actual.as_deref().unwrap_or("nothing"),
+                expected
+            ),
+            Failure::Timeout => write!(f, "timed out"),
+        }
+    }
+}
+
+/// classifies a finished run of a day from its exit code and stderr. `None` if it succeeded.
+pub fn classify(code: Option<i32>, stderr: &str) -> Option<Failure> {
+    if code == Some(0) {
+        return None;
+    }
+    if code == Some(DISAGREEMENT_EXIT_CODE) {
+        if let Some(names) = stderr
+            .lines()
+            .find_map(|line| line.strip_prefix(IMPLEMENTATIONS_PREFIX))
+        {
+            return Some(Failure::Disagreement(
+                names.split(", ").map(String::from).collect(),
+            ));
+        }
+    }
+    // the location identifies a panic. its message may change as the input shrinks.
+    let message = stderr.lines().find_map(|line| {
+        if let Some(index) = line.find("panicked at ") {
+            Some(line[index..].trim_end_matches(':').to_string())
+        } else {
+            line.find("overflowed its stack")
+                .map(|index| line[index..].to_string())
+        }
+    });
+    Some(Failure::Panic(message.unwrap_or_else(|| match code {
+        Some(code) => format!("exited with code {}", code),
+        None => "killed by a signal".into(),
+    })))
+}
+
+/// delta debugging (ddmin): removes ever smaller chunks of `items` as long as `fails` holds
+/// for what remains. `fails` must hold for `items` itself.
+pub fn ddmin<T: Clone>(items: Vec<T>, mut fails: impl FnMut(&[T]) -> bool) -> Vec<T> {
+    let mut items = items;
+    let mut chunks = 2;
+    while items.len() >= 2 {
+        let size = items.len().div_ceil(chunks);
+        let reduced = (0..items.len()).step_by(size).find_map(|start| {
+            let complement = [&items[..start], &items[(start + size).min(items.len())..]].concat();
+            fails(&complement).then_some(complement)
+        });
+        match reduced {
+            Some(complement) => {
+                items = complement;
+                chunks = (chunks - 1).max(2);
+            }
+            None if chunks >= items.len() => break,
+            None => chunks = (chunks * 2).min(items.len()),
+        }
+    }
+    if items.len() == 1 && fails(&[]) {
+        items.clear();
+    }
+    items
+}
+
+/// blocks of lines separated by blank lines, each keeping its trailing blank line.
+fn blocks(lines: &[&str]) -> Vec<Vec<String>> {
+    let mut blocks = vec![Vec::new()];
+    for line in lines {
+        blocks.last_mut().unwrap().push(line.to_string());
+        if line.trim().is_empty() {
+            blocks.push(Vec::new());
+        }
+    }
+    blocks.retain(|block| !block.is_empty());
+    blocks
+}
+
+fn join(lines: &[String], trailing_newline: bool) -> String {
+    let mut text = lines.join("\n");
+    if trailing_newline && !text.is_empty() {
+        text.push('\n');
+    }
+    text
+}
+
+/// shrinks `input` first by blocks, then by single lines, keeping every candidate for which
+/// `fails` holds.
+pub fn shrink(input: &str, mut fails: impl FnMut(&str) -> bool) -> String {
+    let trailing_newline = input.ends_with('\n');
+    let lines = input.lines().collect::<Vec<_>>();
+
+    let blocks = ddmin(blocks(&lines), |blocks| {
+        fails(&join(&blocks.concat(), trailing_newline))
+    });
+    let lines = ddmin(blocks.concat(), |lines| {
+        fails(&join(lines, trailing_newline))
+    });
+    join(&lines, trailing_newline)
+}
+
+/// the binary of `day` that `cargo build --message-format=json` reported building.
+pub fn executable(messages: &str, day: &str) -> Option<PathBuf> {
+    messages
+        .lines()
+        .filter_map(|line| json::parse(line).ok())
+        .filter(|message| {
+            message.get("reason").and_then(json::Value::as_str) == Some("compiler-artifact")
+                && message
+                    .get("target")
+                    .and_then(|target| target.get("name"))
+                    .and_then(json::Value::as_str)
+                    == Some(day)
+        })
+        .find_map(|message| message.get("executable")?.as_str().map(PathBuf::from))
+}
+
+/// the first `src/examples/NN-regression-K.txt` that does not exist yet, and its `K`.
+pub fn regression_path(examples: &Path, day: u8) -> (PathBuf, u32) {
+    (1..)
+        .map(|k| (examples.join(format!("{:02}-regression-{}.txt", day, k)), k))
+        .find(|(path, _)| !path.exists())
+        .unwrap()
+}
+
+/// a test that fails as long as the bug that `cargo shrink` found is there.
+pub fn regression_test(day: u8, part: u8, number: u32, failure: &Failure) -> String {
+    let function = if part == 1 { "part_one" } else { "part_two" };
+    let (attributes, body) = match failure {
+        Failure::Panic(_) => (
+            String::new(),
+            format!("        {}(&input);\n", function),
+        ),
+        Failure::Disagreement(names) => (
+            String::new(),
+            format!(
+                "        let implementations: [advent_of_code::race::Implementation<_>; {}] = [{}];\n        assert_eq!(advent_of_code::race::disagreements(&implementations, &input), vec![]);\n",
+                names.len(),
+                names
+                    .iter()
+                    .map(|name| format!("(\"{0}\", {0})", name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        ),
+        Failure::WrongAnswer { expected, .. } => (
+            String::new(),
+            format!(
+                "        assert_eq!({}(&input).map(|answer| answer.to_string()), Some({:?}.to_string()));\n",
+                function, expected
+            ),
+        ),
+        Failure::Timeout => (
+            "    #[ignore = \"timed out in cargo shrink\"]\n".into(),
+            format!("        {}(&input);\n", function),
+        ),
+    };
+    format!(
+        "\n    // found by `cargo shrink`: {}\n    #[test]\n{}    fn test_regression_{}() {{\n        let input = advent_of_code::read_regression({}, {});\n{}    }}\n",
+        failure, attributes, number, day, number, body
+    )
+}
+
+/// adds `test` at the end of the `tests` module of a day.
+pub fn insert_test(module: &str, test: &str) -> Option<String> {
+    if !module.contains("mod tests") {
+        return None;
+    }
+    let end = module.trim_end().rfind('}')?;
+    Some(format!("{}{}{}", &module[..end], test, &module[end..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minimize() {
+        let items = (0..20).collect::<Vec<_>>();
+        let calls = std::cell::Cell::new(0);
+        let minimal = ddmin(items, |items| {
+            calls.set(calls.get() + 1);
+            items.contains(&3) && items.contains(&17)
+        });
+        assert_eq!(minimal, vec![3, 17]);
+        assert!(calls.get() < 60);
+    }
+
+    #[test]
+    fn shrink_blocks_and_lines() {
+        let input = "1\n2\n\n3\nboom\n4\n\n5\n";
+        assert_eq!(
+            shrink(input, |candidate| candidate.contains("boom")),
+            "boom\n"
+        );
+        assert_eq!(
+            shrink(input, |candidate| candidate.contains("2\n\n3")),
+            "2\n\n3\n"
+        );
+    }
+
+    #[test]
+    fn classify_failures() {
+        assert_eq!(classify(Some(0), ""), None);
+        assert_eq!(
+            classify(
+                Some(101),
+                "thread 'main' panicked at src/bin/08.rs:10:5:\nindex out of bounds\n"
+            ),
+            Some(Failure::Panic("panicked at src/bin/08.rs:10:5".into()))
+        );
+        assert_eq!(
+            classify(Some(3), "implementations: part_one, part_one_brute_force\n"),
+            Some(Failure::Disagreement(vec![
+                "part_one".into(),
+                "part_one_brute_force".into()
+            ]))
+        );
+        assert_eq!(
+            classify(None, ""),
+            Some(Failure::Panic("killed by a signal".into()))
+        );
+    }
+
+    #[test]
+    fn generate_test() {
+        let module = "fn main() {}\n\n#[cfg(test)]\nmod tests {\n    use super::*;\n}\n";
+        let test = regression_test(8, 1, 2, &Failure::Panic("boom".into()));
+        let module = insert_test(module, &test).unwrap();
+        assert!(module.ends_with(
+            "    use super::*;\n\n    // found by `cargo shrink`: boom\n    #[test]\n    fn test_regression_2() {\n        let input = advent_of_code::read_regression(8, 2);\n        part_one(&input);\n    }\n}\n"
+        ));
+        assert_eq!(insert_test("fn main() {}\n", &test), None);
+
+        let failure = Failure::WrongAnswer {
+            expected: "24000".into(),
+            actual: None,
+        };
+        assert_eq!(
+            failure.to_string(),
+            "answered nothing, the known answer is 24000"
+        );
+        assert!(regression_test(1, 2, 1, &failure)
+            .contains("assert_eq!(part_two(&input).map(|answer| answer.to_string()), Some(\"24000\".to_string()));"));
+    }
+
+    #[test]
+    fn read_day_output() {
+        assert_eq!(reported_answer("answer: 42\n"), Some("42".into()));
+        assert_eq!(reported_answer(""), None);
+
+        let messages = r#"{"reason":"compiler-artifact","target":{"name":"advent_of_code"},"executable":null}
+{"reason":"compiler-artifact","target":{"name":"08"},"executable":"/tmp/target/release/08.exe"}
+{"reason":"build-finished","success":true}"#;
+        assert_eq!(
+            executable(messages, "08"),
+            Some(PathBuf::from("/tmp/target/release/08.exe"))
+        );
+        assert_eq!(executable(messages, "09"), None);
+    }
+}
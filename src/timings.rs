@@ -0,0 +1,58 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::{collections::BTreeMap, time::Duration};
+
+use crate::history::Entry;
+
+/// the last time every solved part took, taken from the history that `solve!` writes.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct Timings(BTreeMap<(u8, u8), Duration>);
+
+impl Timings {
+    /// the time of the latest entry of each part.
+    pub fn from_history(entries: &[Entry]) -> Self {
+        Timings(
+            entries
+                .iter()
+                .map(|entry| ((entry.day, entry.part), entry.elapsed))
+                .collect(),
+        )
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<Duration> {
+        self.0.get(&(day, part)).copied()
+    }
+
+    /// total time of both parts, if at least one was measured.
+    pub fn total(&self, day: u8) -> Option<Duration> {
+        match (self.get(day, 1), self.get(day, 2)) {
+            (None, None) => None,
+            (one, two) => Some(one.unwrap_or_default() + two.unwrap_or_default()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn latest_times() {
+        let entry = |day, part, micros| Entry {
+            timestamp: 0,
+            day,
+            part,
+            answer: "1".into(),
+            elapsed: Duration::from_micros(micros),
+            commit: None,
+        };
+        let timings =
+            Timings::from_history(&[entry(7, 2, 1500), entry(1, 1, 3), entry(7, 2, 1200)]);
+        assert_eq!(timings.get(7, 2), Some(Duration::from_micros(1200)));
+        assert_eq!(timings.total(7), Some(Duration::from_micros(1200)));
+        assert_eq!(timings.total(1), Some(Duration::from_micros(3)));
+        assert_eq!(timings.total(2), None);
+    }
+}
@@ -0,0 +1,38 @@
+use advent_of_code::helpers::grid::Grid;
+
+fn parse_grid(input: &str) -> Grid<char> {
+    Grid::parse(input).unwrap()
+}
+
+pub fn part_one(input: &str) -> Option<{{PART_ONE_TYPE}}> {
+    let grid = parse_grid(input);
+    None
+}
+
+pub fn part_two(input: &str) -> Option<{{PART_TWO_TYPE}}> {
+    let grid = parse_grid(input);
+    None
+}
+
+fn main() {
+    let input = &advent_of_code::read_file("inputs", {{DAY}});
+    advent_of_code::solve!(1, part_one, input);
+    advent_of_code::solve!(2, part_two, input);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = advent_of_code::read_file("examples", {{DAY}});
+        assert_eq!(part_one(&input), None);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = advent_of_code::read_file("examples", {{DAY}});
+        assert_eq!(part_two(&input), None);
+    }
+}
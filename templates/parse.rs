@@ -0,0 +1,74 @@
+use advent_of_code::helpers::parse;
+use std::{fmt::Display, str::FromStr};
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+struct Line(String);
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+enum ParseLineError {
+    Empty,
+}
+
+impl Display for ParseLineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseLineError::Empty => write!(f, "empty line"),
+        }
+    }
+}
+
+impl FromStr for Line {
+    type Err = ParseLineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            Err(ParseLineError::Empty)
+        } else {
+            Ok(Line(s.to_string()))
+        }
+    }
+}
+
+fn parse_lines(input: &str) -> Option<Vec<Line>> {
+    parse::parse_lines(input)
+        .map_err(|errors| eprintln!("{}", errors))
+        .ok()
+}
+
+pub fn part_one(input: &str) -> Option<{{PART_ONE_TYPE}}> {
+    let lines = parse_lines(input)?;
+    None
+}
+
+pub fn part_two(input: &str) -> Option<{{PART_TWO_TYPE}}> {
+    let lines = parse_lines(input)?;
+    None
+}
+
+fn main() {
+    let input = &advent_of_code::read_file("inputs", {{DAY}});
+    advent_of_code::solve!(1, part_one, input);
+    advent_of_code::solve!(2, part_two, input);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = advent_of_code::read_file("examples", {{DAY}});
+        assert_eq!(part_one(&input), None);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = advent_of_code::read_file("examples", {{DAY}});
+        assert_eq!(part_two(&input), None);
+    }
+
+    #[test]
+    fn test_parse_line() {
+        assert_eq!("".parse::<Line>(), Err(ParseLineError::Empty));
+    }
+}